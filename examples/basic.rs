@@ -1,9 +1,13 @@
-//! This example uses the asynchronous proxy instances.
+//! This example uses the asynchronous helpers.
+//!
+//! The futures are run with `async-std`, so the example requires the default `async-io` feature.
 //!
 //! The client configuration must be passed as a string blob, including any certificates/keys inline (PEM format). A basic username/password authentication handler is also included.
 //!
 
 use async_std::task;
+use openvpn3_rs::helpers::{OpenVPN3, StaticCredentials};
+use std::time::Duration;
 
 static CONFIG_STR: &str = "
 client
@@ -37,65 +41,23 @@ pull
 
 fn main() {
     task::block_on(async {
-        let connection = zbus::Connection::system().await.unwrap();
-        let config_manager = openvpn3_rs::ConfigurationProxy::new(&connection)
-            .await
-            .unwrap();
-        let config = config_manager
+        let openvpn3 = OpenVPN3::connect().await.unwrap();
+        let config = openvpn3
             .import("My VPN", CONFIG_STR, true, false)
             .await
             .unwrap();
+        let session = config.new_tunnel().await.unwrap();
 
-        let sessions_manager = openvpn3_rs::SessionsProxy::new(&connection).await.unwrap();
-        let session = sessions_manager.new_tunnel(&config.path()).await.unwrap();
-
-        let mut ready = false;
-        while !ready {
-            // If the session is ready, the `ready()` method will return Ok(), otherwise an error will be returned with more details.
-            if let Err(err) = session.ready().await {
-                let err_str = err.to_string();
-                if err_str.contains("Missing user credentials") {
-                    // This loop queries the session for which credentials are needed. This example covers username/password authentication.
-
-                    let ui_type_group = session.user_input_queue_get_type_group().await.unwrap();
-
-                    for (ca_type, ca_group) in ui_type_group {
-                        let ui_queue_ids = session
-                            .user_input_queue_check(ca_type, ca_group)
-                            .await
-                            .unwrap();
+        // Username/password authentication handler. Requests for input are answered until the session is ready, then the connection is started.
+        let credentials = StaticCredentials::new()
+            .username("smith")
+            .password("hunter2");
 
-                        for id in ui_queue_ids {
-                            let (ca_type, ca_group, id, name, _description, _hidden_input) =
-                                session
-                                    .user_input_queue_fetch(ca_type, ca_group, id)
-                                    .await
-                                    .unwrap();
-
-                            if name == "username" {
-                                session
-                                    .user_input_provide(ca_type, ca_group, id, "smith")
-                                    .await
-                                    .unwrap();
-                            }
-
-                            if name == "password" {
-                                session
-                                    .user_input_provide(ca_type, ca_group, id, "hunter2")
-                                    .await
-                                    .unwrap();
-                            }
-                        }
-                    }
-                } else if err_str.contains("Backend VPN process is not ready") {
-                    task::sleep(std::time::Duration::from_secs(1)).await;
-                }
-            } else {
-                ready = true;
-            }
-        }
-
-        session.connect().await.unwrap();
+        // Resolves once the client has connected, or fails with the reason the connection can no longer succeed.
+        session
+            .connect_with(&credentials, Duration::from_secs(30))
+            .await
+            .unwrap();
 
         // wait for signal to disconnect
 
@@ -0,0 +1,426 @@
+//! Checks that the running OpenVPN 3 services match the introspection data this crate was generated from.
+//!
+//! Requires the `diagnostics` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async_std::task::block_on(async {
+//! use openvpn3_rs::{diagnostics, helpers::OpenVPN3};
+//!
+//! let openvpn3 = OpenVPN3::connect().await.unwrap();
+//! let report = diagnostics::verify_interfaces(&openvpn3).await.unwrap();
+//!
+//! if !report.is_compatible() {
+//!     eprintln!("{}", report);
+//! }
+//! # });
+//! ```
+
+use crate::{
+    helpers::{OpenVPN3, Service},
+    ConfigurationProxy, NetCfgProxy, Result, SessionsProxy,
+};
+
+use std::fmt;
+use zbus::{
+    fdo::IntrospectableProxy,
+    xml::{Interface, Node},
+};
+
+/// Kind of an introspected D-Bus interface member.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberKind {
+    Interface,
+    Method,
+    Signal,
+    Property,
+}
+
+impl fmt::Display for MemberKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemberKind::Interface => write!(f, "interface"),
+            MemberKind::Method => write!(f, "method"),
+            MemberKind::Signal => write!(f, "signal"),
+            MemberKind::Property => write!(f, "property"),
+        }
+    }
+}
+
+/// Kind of an introspected object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectKind {
+    /// The main object of a service, e.g. `/net/openvpn/v3/sessions`.
+    Manager,
+    /// An object managed by a service, e.g. a VPN session or a configuration profile.
+    Node,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectKind::Manager => write!(f, "manager"),
+            ObjectKind::Node => write!(f, "node"),
+        }
+    }
+}
+
+/// A difference between the bundled introspection data and a running service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Difference {
+    /// A member of the bundled introspection data is not provided by the service.
+    Missing { kind: MemberKind, name: String },
+    /// The service provides a member which is not in the bundled introspection data.
+    Added { kind: MemberKind, name: String },
+    /// The signature of a member differs.
+    Changed {
+        kind: MemberKind,
+        name: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Missing { kind, name } => write!(f, "missing {} {}", kind, name),
+            Difference::Added { kind, name } => write!(f, "unknown {} {}", kind, name),
+            Difference::Changed {
+                kind,
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} {} changed from `{}` to `{}`",
+                kind, name, expected, found
+            ),
+        }
+    }
+}
+
+/// Result of checking a single object of a service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceReport {
+    pub service: Service,
+    pub object: ObjectKind,
+    /// D-Bus object path of the checked object.
+    pub path: String,
+    /// Whether the object could be introspected.
+    pub available: bool,
+    pub differences: Vec<Difference>,
+}
+
+impl ServiceReport {
+    /// Whether the object is available and provides every member of the bundled introspection data unchanged.
+    ///
+    /// Members which are only provided by the service do not affect compatibility.
+    pub fn is_compatible(&self) -> bool {
+        self.available
+            && self
+                .differences
+                .iter()
+                .all(|difference| matches!(difference, Difference::Added { .. }))
+    }
+}
+
+/// Result of [verify_interfaces].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceReport {
+    pub services: Vec<ServiceReport>,
+    /// Services whose managed objects were not checked, because none was available to the user.
+    pub unchecked: Vec<Service>,
+}
+
+impl InterfaceReport {
+    /// Whether all services are compatible.
+    pub fn is_compatible(&self) -> bool {
+        self.services.iter().all(ServiceReport::is_compatible)
+    }
+
+    /// Report of the main object of a service.
+    pub fn service(&self, service: Service) -> Option<&ServiceReport> {
+        self.object(service, ObjectKind::Manager)
+    }
+
+    /// Report of an object managed by a service, if one was checked.
+    pub fn node(&self, service: Service) -> Option<&ServiceReport> {
+        self.object(service, ObjectKind::Node)
+    }
+
+    fn object(&self, service: Service, object: ObjectKind) -> Option<&ServiceReport> {
+        self.services
+            .iter()
+            .find(|report| report.service == service && report.object == object)
+    }
+}
+
+impl fmt::Display for InterfaceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.services {
+            let name = match report.object {
+                ObjectKind::Manager => report.service.to_string(),
+                ObjectKind::Node => format!("{} {}", report.service, report.path),
+            };
+
+            if !report.available {
+                writeln!(f, "{}: not available", name)?;
+            }
+
+            for difference in &report.differences {
+                writeln!(f, "{}: {}", name, difference)?;
+            }
+        }
+
+        for service in &self.unchecked {
+            writeln!(
+                f,
+                "{} {}: not checked (no object available)",
+                service,
+                ObjectKind::Node
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Bundled introspection data of an object of a service.
+fn bundled_xml(service: Service, object: ObjectKind) -> &'static str {
+    match (service, object) {
+        (Service::Sessions, ObjectKind::Manager) => {
+            include_str!("../xml/net.openvpn.v3.sessions.xml")
+        }
+        (Service::Sessions, ObjectKind::Node) => {
+            include_str!("../xml/net.openvpn.v3.sessions.node.xml")
+        }
+        (Service::Configuration, ObjectKind::Manager) => {
+            include_str!("../xml/net.openvpn.v3.configuration.xml")
+        }
+        (Service::Configuration, ObjectKind::Node) => {
+            include_str!("../xml/net.openvpn.v3.configuration.node.xml")
+        }
+        (Service::Log, _) => include_str!("../xml/net.openvpn.v3.log.xml"),
+        (Service::NetCfg, ObjectKind::Manager) => include_str!("../xml/net.openvpn.v3.netcfg.xml"),
+        (Service::NetCfg, ObjectKind::Node) => {
+            include_str!("../xml/net.openvpn.v3.netcfg.node.xml")
+        }
+    }
+}
+
+/// Signatures of the members of an interface, keyed by kind and name.
+fn members(interface: &Interface) -> Vec<(MemberKind, String, String)> {
+    let args = |args: Vec<&zbus::xml::Arg>, direction: &str| -> String {
+        args.iter()
+            .filter(|arg| arg.direction().unwrap_or("in") == direction)
+            .map(|arg| arg.ty())
+            .collect()
+    };
+
+    let methods = interface.methods().into_iter().map(|method| {
+        (
+            MemberKind::Method,
+            method.name().to_string(),
+            format!(
+                "({}) -> ({})",
+                args(method.args(), "in"),
+                args(method.args(), "out")
+            ),
+        )
+    });
+    let signals = interface.signals().into_iter().map(|signal| {
+        (
+            MemberKind::Signal,
+            signal.name().to_string(),
+            format!(
+                "({})",
+                signal.args().iter().map(|arg| arg.ty()).collect::<String>()
+            ),
+        )
+    });
+    let properties = interface.properties().into_iter().map(|property| {
+        (
+            MemberKind::Property,
+            property.name().to_string(),
+            format!("{} {}", property.ty(), property.access()),
+        )
+    });
+
+    methods.chain(signals).chain(properties).collect()
+}
+
+/// Compare an interface of the bundled introspection data with the same interface of a running service.
+fn diff(expected: Option<&Interface>, found: Option<&Interface>, name: &str) -> Vec<Difference> {
+    let (expected, found) = match (expected, found) {
+        (Some(expected), Some(found)) => (members(expected), members(found)),
+        (Some(_), None) => {
+            return vec![Difference::Missing {
+                kind: MemberKind::Interface,
+                name: name.to_string(),
+            }]
+        }
+        (None, Some(_)) => {
+            return vec![Difference::Added {
+                kind: MemberKind::Interface,
+                name: name.to_string(),
+            }]
+        }
+        (None, None) => return Vec::new(),
+    };
+
+    let mut differences = Vec::new();
+
+    for (kind, name, signature) in &expected {
+        match found.iter().find(|(k, n, _)| k == kind && n == name) {
+            None => differences.push(Difference::Missing {
+                kind: *kind,
+                name: name.clone(),
+            }),
+            Some((_, _, live)) if live != signature => differences.push(Difference::Changed {
+                kind: *kind,
+                name: name.clone(),
+                expected: signature.clone(),
+                found: live.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for (kind, name, _) in &found {
+        if !expected.iter().any(|(k, n, _)| k == kind && n == name) {
+            differences.push(Difference::Added {
+                kind: *kind,
+                name: name.clone(),
+            });
+        }
+    }
+
+    differences
+}
+
+fn interface<'n>(node: &'n Node, name: &str) -> Option<&'n Interface> {
+    node.interfaces()
+        .into_iter()
+        .find(|interface| interface.name() == name)
+}
+
+/// Introspect the objects of each OpenVPN 3 service and compare their interfaces with the introspection data bundled with this crate.
+///
+/// Besides the main object of each service, one object managed by the session manager, the configuration manager and the network configuration service is checked: the first VPN session, configuration profile and virtual network interface available to the user. Nothing is created to be checked, so services without such an object are listed in [InterfaceReport::unchecked].
+///
+/// Only the OpenVPN 3 interface of each object is compared, the standard `org.freedesktop.DBus.*` interfaces are ignored.
+///
+/// # Arguments
+///
+/// * `openvpn3` - Client whose bus connection and service destinations are used.
+///
+/// # Returns
+///
+/// An [InterfaceReport] with one [ServiceReport] per checked object. Services which cannot be introspected are reported as unavailable.
+pub async fn verify_interfaces(openvpn3: &OpenVPN3<'_>) -> Result<InterfaceReport> {
+    let mut services = Vec::new();
+    let mut unchecked = Vec::new();
+
+    for service in Service::all() {
+        let report = verify_object(openvpn3, service, ObjectKind::Manager, service.path()).await?;
+        let available = report.available;
+        services.push(report);
+
+        if available && service != Service::Log {
+            match verify_node(openvpn3, service).await? {
+                Some(report) => services.push(report),
+                None => unchecked.push(service),
+            }
+        }
+    }
+
+    Ok(InterfaceReport {
+        services,
+        unchecked,
+    })
+}
+
+/// Check the first object managed by a service, if there is one.
+///
+/// Returns `None` if the service has no object available to the user.
+async fn verify_node(openvpn3: &OpenVPN3<'_>, service: Service) -> Result<Option<ServiceReport>> {
+    let connection = openvpn3.connection();
+
+    let paths = match service {
+        Service::Sessions => {
+            SessionsProxy::builder(connection)
+                .destination(openvpn3.destination(service).to_owned())?
+                .build()
+                .await?
+                .fetch_available_sessions()
+                .await
+        }
+        Service::Configuration => {
+            ConfigurationProxy::builder(connection)
+                .destination(openvpn3.destination(service).to_owned())?
+                .build()
+                .await?
+                .fetch_available_configs()
+                .await
+        }
+        Service::NetCfg => {
+            NetCfgProxy::builder(connection)
+                .destination(openvpn3.destination(service).to_owned())?
+                .build()
+                .await?
+                .fetch_interface_list()
+                .await
+        }
+        Service::Log => return Ok(None),
+    };
+
+    match paths.unwrap_or_default().first() {
+        Some(path) => verify_object(openvpn3, service, ObjectKind::Node, path.as_str())
+            .await
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Introspect an object and compare its OpenVPN 3 interface with the bundled introspection data.
+async fn verify_object(
+    openvpn3: &OpenVPN3<'_>,
+    service: Service,
+    object: ObjectKind,
+    path: &str,
+) -> Result<ServiceReport> {
+    let bundled = Node::from_reader(bundled_xml(service, object).as_bytes())?;
+
+    let (available, differences) = match introspect(openvpn3, service, path).await {
+        Ok(xml) => {
+            let live = Node::from_reader(xml.as_bytes())?;
+            let differences = diff(
+                interface(&bundled, service.interface()),
+                interface(&live, service.interface()),
+                service.interface(),
+            );
+            (true, differences)
+        }
+        Err(_) => (false, Vec::new()),
+    };
+
+    Ok(ServiceReport {
+        service,
+        object,
+        path: path.to_string(),
+        available,
+        differences,
+    })
+}
+
+async fn introspect(openvpn3: &OpenVPN3<'_>, service: Service, path: &str) -> Result<String> {
+    let proxy = IntrospectableProxy::builder(openvpn3.connection())
+        .destination(openvpn3.destination(service).to_owned())?
+        .path(path.to_owned())?
+        .build()
+        .await?;
+
+    Ok(proxy.introspect().await?)
+}
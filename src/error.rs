@@ -1,24 +1,128 @@
+use crate::{helpers::Service, profile::FileError};
+
 use std::fmt;
+use zbus::DBusError;
 
 #[derive(Debug)]
 pub enum Error {
     Zbus(zbus::Error),
     Fdo(zbus::fdo::Error),
     Json(serde_json::Error),
+    Io(std::io::Error),
     /// Data mismatch when fetching user input queue slots
     UserInputSlotMismatch,
     BackendNotReady,
     MissingUserCredentials,
+    /// The server rejected the provided credentials
+    AuthFailed(String),
+    /// The client connection failed
+    ConnectionFailed(String),
+    /// The configuration profile could not be parsed by the backend
+    InvalidConfiguration(String),
+    /// The backend VPN process stopped unexpectedly
+    BackendKilled(String),
+    /// The operation did not complete in time
+    Timeout,
+    /// A challenge sent by the server could not be parsed
+    InvalidChallenge(String),
+    /// An OpenVPN 3 service did not respond
+    ServiceUnavailable {
+        service: Service,
+    },
+    /// The caller is not allowed to access the object (`net.openvpn.v3.error.acl.denied`)
+    AccessDenied(String),
+    /// The requested object does not exist (`net.openvpn.v3.error.path`)
+    NotFound(String),
+    /// The service rejected a value passed to it (`net.openvpn.v3.error.input_queue`)
+    InvalidInput(String),
+    /// Error reported by the session manager (`net.openvpn.v3.sessions.error`)
+    SessionManager(String),
+    /// Error reported by the configuration manager (`net.openvpn.v3.configuration.error`)
+    ConfigurationManager(String),
+    /// Error reported by a backend VPN process (`net.openvpn.v3.backend.error`)
+    Backend(String),
+    /// The running OpenVPN 3 services do not support a feature
+    Unsupported(String),
+    /// A configuration profile could not be parsed locally
+    InvalidProfile {
+        line: usize,
+        message: String,
+    },
+    /// Files referenced by a configuration profile could not be read
+    UnreadableFiles(Vec<FileError>),
+    /// A user name could not be resolved to a UID
+    UnknownUser(String),
+}
+
+impl Error {
+    /// Map a D-Bus error reply to a typed error by its error name.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the error name is not known to this crate.
+    fn from_error_name(name: &str, description: Option<&str>) -> Option<Self> {
+        let description = description.unwrap_or_default().to_string();
+
+        match name {
+            "net.openvpn.v3.error.acl.denied" | "org.freedesktop.DBus.Error.AccessDenied" => {
+                Some(Error::AccessDenied(description))
+            }
+            "net.openvpn.v3.error.path" | "org.freedesktop.DBus.Error.UnknownObject" => {
+                Some(Error::NotFound(description))
+            }
+            "net.openvpn.v3.error.input_queue" | "org.freedesktop.DBus.Error.InvalidArgs" => {
+                Some(Error::InvalidInput(description))
+            }
+            "net.openvpn.v3.error.ready" => Some(Error::MissingUserCredentials),
+            "net.openvpn.v3.sessions.error" => Some(Error::SessionManager(description)),
+            "net.openvpn.v3.configuration.error" => Some(Error::ConfigurationManager(description)),
+            "net.openvpn.v3.backend.error" => Some(Error::Backend(description)),
+            _ => None,
+        }
+    }
 }
 
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Error::Zbus(_), Error::Zbus(_)) => true,
-            (Error::Json(_), Error::Json(_)) => true,
+            (Error::Zbus(a), Error::Zbus(b)) => a == b,
+            (Error::Fdo(a), Error::Fdo(b)) => a == b,
+            (Error::Json(a), Error::Json(b)) => {
+                a.classify() == b.classify() && a.line() == b.line() && a.column() == b.column()
+            }
+            (Error::Io(a), Error::Io(b)) => a.kind() == b.kind(),
             (Error::UserInputSlotMismatch, Error::UserInputSlotMismatch) => true,
             (Error::BackendNotReady, Error::BackendNotReady) => true,
             (Error::MissingUserCredentials, Error::MissingUserCredentials) => true,
+            (Error::AuthFailed(a), Error::AuthFailed(b)) => a == b,
+            (Error::ConnectionFailed(a), Error::ConnectionFailed(b)) => a == b,
+            (Error::InvalidConfiguration(a), Error::InvalidConfiguration(b)) => a == b,
+            (Error::BackendKilled(a), Error::BackendKilled(b)) => a == b,
+            (Error::Timeout, Error::Timeout) => true,
+            (Error::InvalidChallenge(a), Error::InvalidChallenge(b)) => a == b,
+            (
+                Error::ServiceUnavailable { service: a },
+                Error::ServiceUnavailable { service: b },
+            ) => a == b,
+            (Error::AccessDenied(a), Error::AccessDenied(b)) => a == b,
+            (Error::NotFound(a), Error::NotFound(b)) => a == b,
+            (Error::InvalidInput(a), Error::InvalidInput(b)) => a == b,
+            (Error::SessionManager(a), Error::SessionManager(b)) => a == b,
+            (Error::ConfigurationManager(a), Error::ConfigurationManager(b)) => a == b,
+            (Error::Backend(a), Error::Backend(b)) => a == b,
+            (Error::Unsupported(a), Error::Unsupported(b)) => a == b,
+            (
+                Error::InvalidProfile {
+                    line: a,
+                    message: x,
+                },
+                Error::InvalidProfile {
+                    line: b,
+                    message: y,
+                },
+            ) => a == b && x == y,
+            (Error::UnreadableFiles(a), Error::UnreadableFiles(b)) => a == b,
+            (Error::UnknownUser(a), Error::UnknownUser(b)) => a == b,
             (_, _) => false,
         }
     }
@@ -26,13 +130,24 @@ impl PartialEq for Error {
 
 impl From<zbus::Error> for Error {
     fn from(err: zbus::Error) -> Self {
-        Self::Zbus(err)
+        match err {
+            zbus::Error::MethodError(ref name, ref description, _) => {
+                Self::from_error_name(name.as_str(), description.as_deref())
+                    .unwrap_or(Self::Zbus(err))
+            }
+            zbus::Error::FDO(err) => Self::from(*err),
+            err => Self::Zbus(err),
+        }
     }
 }
 
 impl From<zbus::fdo::Error> for Error {
     fn from(err: zbus::fdo::Error) -> Self {
-        Self::Fdo(err)
+        match err {
+            zbus::fdo::Error::ZBus(err) => Self::from(err),
+            err => Self::from_error_name(err.name().as_str(), err.description())
+                .unwrap_or(Self::Fdo(err)),
+        }
     }
 }
 
@@ -42,15 +157,82 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::Zbus(err) => write!(f, "D-Bus Error: {:?}", err),
             Error::Fdo(err) => write!(f, "D-Bus Error: {:?}", err),
             Error::Json(err) => write!(f, "JSON Error: {}", err),
+            Error::Io(err) => write!(f, "I/O Error: {}", err),
             Error::UserInputSlotMismatch => write!(f, "Mismatch in User Input Queue Slot"),
             Error::BackendNotReady => write!(f, "Backend VPN process is not ready"),
             Error::MissingUserCredentials => write!(f, "Missing user credentials"),
+            Error::AuthFailed(msg) => write!(f, "Authentication failed: {}", msg),
+            Error::ConnectionFailed(msg) => write!(f, "Connection failed: {}", msg),
+            Error::InvalidConfiguration(msg) => write!(f, "Configuration error: {}", msg),
+            Error::BackendKilled(msg) => write!(f, "Backend VPN process killed: {}", msg),
+            Error::Timeout => write!(f, "Operation timed out"),
+            Error::InvalidChallenge(msg) => write!(f, "Invalid challenge: {}", msg),
+            Error::ServiceUnavailable { service } => {
+                write!(f, "OpenVPN 3 service unavailable: {}", service)
+            }
+            Error::AccessDenied(msg) => write!(f, "Access denied: {}", msg),
+            Error::NotFound(msg) => write!(f, "Not found: {}", msg),
+            Error::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            Error::SessionManager(msg) => write!(f, "Session manager error: {}", msg),
+            Error::ConfigurationManager(msg) => {
+                write!(f, "Configuration manager error: {}", msg)
+            }
+            Error::Backend(msg) => write!(f, "Backend error: {}", msg),
+            Error::Unsupported(feature) => {
+                write!(f, "Not supported by the OpenVPN 3 services: {}", feature)
+            }
+            Error::InvalidProfile { line, message } => {
+                write!(f, "Invalid profile on line {}: {}", line, message)
+            }
+            Error::UnreadableFiles(errors) => {
+                write!(f, "Could not read files referenced by the profile")?;
+                for (i, error) in errors.iter().enumerate() {
+                    write!(f, "{} {}", if i == 0 { ":" } else { "," }, error)?;
+                }
+                Ok(())
+            }
+            Error::UnknownUser(name) => write!(f, "Unknown user: {}", name),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_errors() {
+        assert_eq!(
+            Error::from_error_name(
+                "net.openvpn.v3.error.ready",
+                Some("Missing user credentials")
+            ),
+            Some(Error::MissingUserCredentials)
+        );
+        assert_eq!(
+            Error::from_error_name("net.openvpn.v3.error.ready", None),
+            Some(Error::MissingUserCredentials)
+        );
+        assert_eq!(
+            Error::from_error_name(
+                "net.openvpn.v3.sessions.error",
+                Some("Backend VPN process is not ready")
+            ),
+            Some(Error::SessionManager(String::from(
+                "Backend VPN process is not ready"
+            )))
+        );
+    }
+}
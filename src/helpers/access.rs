@@ -0,0 +1,192 @@
+//! Access control of configuration profiles.
+
+use crate::{Error, Result};
+
+use std::fmt;
+
+/// User
+///
+/// A user account to grant access to, or transfer a configuration profile to. Names are resolved to UIDs using the system's user database, which requires the `users` feature.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::User;
+///
+/// assert_eq!(User::from(1000).uid().unwrap(), 1000);
+/// # #[cfg(feature = "users")]
+/// # {
+/// assert_eq!(User::from("root").uid().unwrap(), 0);
+/// assert!(User::from("no-such-user").uid().is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum User {
+    /// A user ID.
+    Uid(u32),
+    /// A user name, e.g. `alice`.
+    Name(String),
+}
+
+impl User {
+    /// Resolve the UID of the user.
+    ///
+    /// Looking up a name may block, e.g. on a network user database.
+    ///
+    /// # Returns
+    ///
+    /// [Error::UnknownUser] if there is no user with this name, or [Error::InvalidInput] for a name without the `users` feature.
+    pub fn uid(&self) -> Result<u32> {
+        match self {
+            User::Uid(uid) => Ok(*uid),
+            #[cfg(feature = "users")]
+            User::Name(name) => nix::unistd::User::from_name(name)
+                .ok()
+                .flatten()
+                .map(|user| user.uid.as_raw())
+                .ok_or_else(|| Error::UnknownUser(name.clone())),
+            #[cfg(not(feature = "users"))]
+            User::Name(_) => Err(Error::InvalidInput(String::from(
+                "user names can only be resolved with the `users` feature",
+            ))),
+        }
+    }
+
+    /// Resolve the UID of the user on a separate thread, so the async runtime is not blocked by the user database.
+    pub(crate) async fn resolve(self) -> Result<u32> {
+        match self {
+            User::Uid(uid) => Ok(uid),
+            #[cfg(feature = "users")]
+            user => crate::runtime::unblock(move || user.uid()).await,
+            #[cfg(not(feature = "users"))]
+            user => user.uid(),
+        }
+    }
+}
+
+impl From<u32> for User {
+    fn from(uid: u32) -> Self {
+        User::Uid(uid)
+    }
+}
+
+impl From<&str> for User {
+    fn from(name: &str) -> Self {
+        User::Name(name.to_string())
+    }
+}
+
+impl From<String> for User {
+    fn from(name: String) -> Self {
+        User::Name(name)
+    }
+}
+
+#[cfg(feature = "users")]
+impl From<&nix::unistd::User> for User {
+    fn from(user: &nix::unistd::User) -> Self {
+        User::Uid(user.uid.as_raw())
+    }
+}
+
+#[cfg(feature = "users")]
+impl From<nix::unistd::User> for User {
+    fn from(user: nix::unistd::User) -> Self {
+        User::Uid(user.uid.as_raw())
+    }
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            User::Uid(uid) => write!(f, "{}", uid),
+            User::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Access Entry
+///
+/// A user listed in a [ConfigAccess], with the name looked up when the snapshot was taken.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AccessEntry {
+    pub uid: u32,
+    /// User name, or `None` if the UID has no entry in the user database or the `users` feature is disabled.
+    pub name: Option<String>,
+}
+
+impl AccessEntry {
+    /// Look up the names of users, on a separate thread so the async runtime is not blocked by the user database.
+    pub(crate) async fn lookup(uids: Vec<u32>) -> Vec<Self> {
+        #[cfg(feature = "users")]
+        return crate::runtime::unblock(move || {
+            uids.into_iter()
+                .map(|uid| Self {
+                    uid,
+                    name: nix::unistd::User::from_uid(nix::unistd::Uid::from_raw(uid))
+                        .ok()
+                        .flatten()
+                        .map(|user| user.name),
+                })
+                .collect()
+        })
+        .await;
+
+        #[cfg(not(feature = "users"))]
+        uids.into_iter()
+            .map(|uid| Self { uid, name: None })
+            .collect()
+    }
+}
+
+impl fmt::Display for AccessEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, self.uid),
+            None => write!(f, "{}", self.uid),
+        }
+    }
+}
+
+/// Configuration Access
+///
+/// Snapshot of who can use a configuration profile, and how it is protected, see [Configuration::access](super::Configuration::access).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigAccess {
+    /// The user which imported the profile, or it was transferred to.
+    pub owner: AccessEntry,
+    /// Users granted access in addition to the owner.
+    pub acl: Vec<AccessEntry>,
+    /// Whether all users can use the profile.
+    pub public_access: bool,
+    /// Whether users other than the owner can only start tunnels, but not retrieve the profile.
+    pub locked_down: bool,
+    /// Whether the profile is read-only and cannot be removed.
+    pub sealed: bool,
+}
+
+impl ConfigAccess {
+    /// Check whether a user can use the profile.
+    pub fn has_access(&self, uid: u32) -> bool {
+        self.public_access || self.owner.uid == uid || self.acl.iter().any(|user| user.uid == uid)
+    }
+}
+
+impl fmt::Display for ConfigAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+        writeln!(f, "owner: {}", self.owner)?;
+        write!(f, "granted:")?;
+        if self.acl.is_empty() {
+            write!(f, " -")?;
+        }
+        for (i, user) in self.acl.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { "," }, user)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "public access: {}", yes_no(self.public_access))?;
+        writeln!(f, "locked down: {}", yes_no(self.locked_down))?;
+        writeln!(f, "sealed: {}", yes_no(self.sealed))
+    }
+}
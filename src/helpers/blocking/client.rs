@@ -0,0 +1,194 @@
+use super::{Configuration, Iter, LogService, Session};
+
+use crate::{
+    helpers::{self, LogEvent, OpenVPN3Builder, Service, ServiceVersion, ServiceVersions},
+    profile::Profile,
+    runtime::{self, block_on},
+    sessions::{Log, SessionManagerEvent},
+    NetCfgProxyBlocking, Result,
+};
+
+use std::{path::Path, time::Duration};
+use zbus::blocking::Connection;
+
+/// OpenVPN 3 Client
+///
+/// Blocking counterpart of [helpers::OpenVPN3].
+#[derive(Clone, Debug)]
+pub struct OpenVPN3<'a> {
+    inner: Option<helpers::OpenVPN3<'a>>,
+}
+
+impl<'a> OpenVPN3<'a> {
+    /// Create a new `OpenVPN3` instance connected to the system bus.
+    pub fn connect() -> Result<OpenVPN3<'a>> {
+        block_on(helpers::OpenVPN3::connect()).map(Self::from)
+    }
+
+    /// Create a new `OpenVPN3` instance using an existing D-Bus [Connection].
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - D-Bus [Connection] to the bus the OpenVPN 3 services are available on.
+    pub fn new(connection: Connection) -> Result<OpenVPN3<'a>> {
+        block_on(helpers::OpenVPN3::new(connection.into_inner())).map(Self::from)
+    }
+
+    /// Construct an `OpenVPN3` instance from an [OpenVPN3Builder].
+    pub fn build(builder: OpenVPN3Builder) -> Result<OpenVPN3<'a>> {
+        block_on(builder.build()).map(Self::from)
+    }
+
+    /// Get the underlying async [helpers::OpenVPN3].
+    pub fn inner(&self) -> &helpers::OpenVPN3<'a> {
+        self.inner.as_ref().expect("inner OpenVPN3 is None")
+    }
+
+    /// Consume `self`, returning the underlying async [helpers::OpenVPN3].
+    pub fn into_inner(mut self) -> helpers::OpenVPN3<'a> {
+        self.inner.take().expect("inner OpenVPN3 is None")
+    }
+
+    /// Get the underlying D-Bus [Connection].
+    pub fn connection(&self) -> Connection {
+        Connection::from(self.inner().connection().clone())
+    }
+
+    /// Fetch all available configuration profiles that are available to the user.
+    pub fn configurations(&'a self) -> Result<Vec<Configuration<'a>>> {
+        Ok(block_on(self.inner().configurations())?
+            .into_iter()
+            .map(Configuration::from)
+            .collect())
+    }
+
+    /// Parse and import a new configuration profile.
+    ///
+    /// See [helpers::OpenVPN3::import].
+    pub fn import<'c>(
+        &self,
+        name: &str,
+        config_str: &str,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<Configuration<'c>> {
+        block_on(
+            self.inner()
+                .import(name, config_str, single_use, persistent),
+        )
+        .map(Configuration::from)
+    }
+
+    /// Import a parsed configuration profile.
+    pub fn import_profile<'c>(
+        &self,
+        name: &str,
+        profile: &Profile,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<Configuration<'c>> {
+        block_on(
+            self.inner()
+                .import_profile(name, profile, single_use, persistent),
+        )
+        .map(Configuration::from)
+    }
+
+    /// Import a configuration profile from a file.
+    ///
+    /// See [helpers::OpenVPN3::import_file].
+    pub fn import_file<'c, P: AsRef<Path>>(
+        &self,
+        path: P,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<Configuration<'c>> {
+        block_on(self.inner().import_file(path, single_use, persistent)).map(Configuration::from)
+    }
+
+    /// Fetch all available sessions that are available to the user.
+    pub fn sessions(&'a self) -> Result<Vec<Session<'a>>> {
+        Ok(block_on(self.inner().sessions())?
+            .into_iter()
+            .map(Session::from)
+            .collect())
+    }
+
+    /// Fetch the names of the virtual network interfaces managed by the session manager.
+    pub fn interfaces(&'a self) -> Result<Vec<String>> {
+        block_on(self.inner().interfaces())
+    }
+
+    /// Get a proxy for the network configuration service.
+    pub fn net_cfg_manager(&self) -> Result<NetCfgProxyBlocking<'static>> {
+        Ok(NetCfgProxyBlocking::from(
+            block_on(self.inner().net_cfg_manager())?.into_inner(),
+        ))
+    }
+
+    /// Get a [LogService] to inspect and configure the OpenVPN 3 log service.
+    pub fn log_service(&self) -> Result<LogService<'static>> {
+        block_on(self.inner().log_service()).map(LogService::from)
+    }
+
+    /// Iterate over the SessionManagerEvent signals of the session manager.
+    pub fn event_stream(&self) -> Result<Iter<'a, SessionManagerEvent>> {
+        block_on(self.inner().event_stream()).map(Iter::new)
+    }
+
+    /// Iterate over the Log signals of the session manager.
+    pub fn log_stream(&self) -> Result<Iter<'a, Log>> {
+        block_on(self.inner().log_stream()).map(Iter::new)
+    }
+
+    /// Iterate over the [LogEvent]s sent by the session manager, the configuration manager and the network configuration service.
+    pub fn all_logs(&self) -> Result<Iter<'a, LogEvent>> {
+        block_on(self.inner().all_logs()).map(Iter::new)
+    }
+
+    /// Wait until the OpenVPN 3 services respond.
+    ///
+    /// See [helpers::OpenVPN3::wait_for_services].
+    pub fn wait_for_services(&self, timeout: Duration) -> Result<()> {
+        block_on(self.inner().wait_for_services(timeout))
+    }
+
+    /// Fetch the version of an OpenVPN 3 service.
+    pub fn service_version(&self, service: Service) -> Result<ServiceVersion> {
+        block_on(self.inner().service_version(service))
+    }
+
+    /// Fetch the versions of all OpenVPN 3 services.
+    pub fn service_versions(&self) -> Result<ServiceVersions> {
+        block_on(self.inner().service_versions())
+    }
+
+    /// Check whether the services support Data Channel Offload.
+    pub fn supports_dco(&self) -> Result<bool> {
+        block_on(self.inner().supports_dco())
+    }
+
+    /// Check whether the log service can proxy Log events to other D-Bus services.
+    pub fn supports_log_proxy(&self) -> Result<bool> {
+        block_on(self.inner().supports_log_proxy())
+    }
+
+    /// Check whether the Data Channel Offload kernel module is available.
+    ///
+    /// See [helpers::OpenVPN3::dco_available].
+    pub fn dco_available(&self) -> Result<bool> {
+        block_on(self.inner().dco_available())
+    }
+}
+
+impl<'a> From<helpers::OpenVPN3<'a>> for OpenVPN3<'a> {
+    fn from(inner: helpers::OpenVPN3<'a>) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+impl Drop for OpenVPN3<'_> {
+    fn drop(&mut self) {
+        runtime::enter(|| drop(self.inner.take()));
+    }
+}
@@ -0,0 +1,171 @@
+use super::Session;
+
+use crate::{
+    helpers::{self, ConfigAccess, ConfigOverride, OverrideKind, StaticChallenge, User},
+    profile::{Profile, ValidationReport},
+    runtime::{self, block_on},
+    Result,
+};
+
+use zbus::{
+    blocking::Connection,
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
+
+/// OpenVPN 3 Configuration
+///
+/// Blocking counterpart of [helpers::Configuration].
+#[derive(Clone, Debug)]
+pub struct Configuration<'a> {
+    inner: Option<helpers::Configuration<'a>>,
+}
+
+impl<'a> Configuration<'a> {
+    /// Constructs a new [Configuration] that represents a single OpenVPN 3 VPN configuration profile through the D-Bus API.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - D-Bus [Connection] instance.
+    /// * `path` - D-Bus [OwnedObjectPath] to the configuration profile.
+    pub fn new(connection: Connection, path: OwnedObjectPath) -> Result<Configuration<'a>> {
+        block_on(helpers::Configuration::new(connection.into_inner(), path)).map(Self::from)
+    }
+
+    /// Get the underlying async [helpers::Configuration].
+    pub fn inner(&self) -> &helpers::Configuration<'a> {
+        self.inner.as_ref().expect("inner Configuration is None")
+    }
+
+    /// Consume `self`, returning the underlying async [helpers::Configuration].
+    pub fn into_inner(mut self) -> helpers::Configuration<'a> {
+        self.inner.take().expect("inner Configuration is None")
+    }
+
+    /// Start a new VPN backend client process for this VPN configuration profile.
+    pub fn new_tunnel<'c>(&self) -> Result<Session<'c>> {
+        block_on(self.inner().new_tunnel()).map(Session::from)
+    }
+
+    /// Fetch the configuration as a string blob.
+    pub fn fetch(&'a self) -> Result<String> {
+        block_on(self.inner().fetch())
+    }
+
+    /// Fetch the configuration as a JSON value.
+    pub fn json(&'a self) -> Result<serde_json::Value> {
+        block_on(self.inner().json())
+    }
+
+    /// Fetch the configuration and parse it as a [Profile].
+    pub fn profile(&'a self) -> Result<Profile> {
+        block_on(self.inner().profile())
+    }
+
+    /// Fetch the configuration and check it for problems.
+    ///
+    /// See [helpers::Configuration::validate].
+    pub fn validate(&'a self) -> Result<ValidationReport> {
+        block_on(self.inner().validate())
+    }
+
+    /// Fetch the [StaticChallenge] defined by this configuration profile, if any.
+    pub fn static_challenge(&'a self) -> Result<Option<StaticChallenge>> {
+        block_on(self.inner().static_challenge())
+    }
+
+    /// Set an override, replacing its previous value.
+    pub fn set_override(&'a self, setting: &ConfigOverride) -> Result<()> {
+        block_on(self.inner().set_override(setting))
+    }
+
+    /// Get all overrides which are set.
+    ///
+    /// See [helpers::Configuration::overrides].
+    pub fn overrides(&'a self) -> Result<Vec<ConfigOverride>> {
+        block_on(self.inner().overrides())
+    }
+
+    /// Remove an override, restoring the setting of the profile.
+    pub fn unset_override(&'a self, kind: OverrideKind) -> Result<()> {
+        block_on(self.inner().unset_override(kind))
+    }
+
+    /// Check whether sessions started from this profile use Data Channel Offload.
+    ///
+    /// See [helpers::Configuration::dco].
+    pub fn dco(&self) -> Result<bool> {
+        block_on(self.inner().dco())
+    }
+
+    /// Enable or disable Data Channel Offload for sessions started from this profile.
+    ///
+    /// See [helpers::Configuration::set_dco].
+    pub fn set_dco(&self, enabled: bool) -> Result<()> {
+        block_on(self.inner().set_dco(enabled))
+    }
+
+    /// Get a snapshot of who can use this profile, and how it is protected.
+    pub fn access(&'a self) -> Result<ConfigAccess> {
+        block_on(self.inner().access())
+    }
+
+    /// Grant a user access to this profile.
+    pub fn grant<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        block_on(self.inner().grant(user))
+    }
+
+    /// Revoke the access of a user to this profile.
+    pub fn revoke<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        block_on(self.inner().revoke(user))
+    }
+
+    /// Allow or disallow all users to use this profile.
+    pub fn set_public(&'a self, public: bool) -> Result<()> {
+        block_on(self.inner().set_public(public))
+    }
+
+    /// Restrict users other than the owner to starting tunnels.
+    ///
+    /// See [helpers::Configuration::lock_down].
+    pub fn lock_down(&'a self, locked_down: bool) -> Result<()> {
+        block_on(self.inner().lock_down(locked_down))
+    }
+
+    /// Make this profile read-only. This cannot be undone.
+    pub fn seal(&'a self) -> Result<()> {
+        block_on(self.inner().seal())
+    }
+
+    /// Transfer the ownership of this profile to another user.
+    ///
+    /// See [helpers::Configuration::transfer_to].
+    pub fn transfer_to<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        block_on(self.inner().transfer_to(user))
+    }
+
+    /// Removes this VPN configuration profile.
+    pub fn remove(&'a self) -> Result<()> {
+        block_on(self.inner().remove())
+    }
+
+    /// Get a property value from the underlying D-Bus proxy.
+    pub fn get_property<T>(&'a self, property_name: &str) -> Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        block_on(self.inner().get_property(property_name))
+    }
+}
+
+impl<'a> From<helpers::Configuration<'a>> for Configuration<'a> {
+    fn from(inner: helpers::Configuration<'a>) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+impl Drop for Configuration<'_> {
+    fn drop(&mut self) {
+        runtime::enter(|| drop(self.inner.take()));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    helpers::{self, LogSubscriber},
+    log::constants::LogLevel,
+    runtime::{self, block_on},
+    LogNodeProxyBlocking, Result,
+};
+
+use zbus::zvariant::ObjectPath;
+
+/// OpenVPN 3 Log Service
+///
+/// Blocking counterpart of [helpers::LogService].
+#[derive(Clone, Debug)]
+pub struct LogService<'a> {
+    inner: Option<helpers::LogService<'a>>,
+}
+
+impl<'a> LogService<'a> {
+    /// Get the underlying async [helpers::LogService].
+    pub fn inner(&self) -> &helpers::LogService<'a> {
+        self.inner.as_ref().expect("inner LogService is None")
+    }
+
+    /// Consume `self`, returning the underlying async [helpers::LogService].
+    pub fn into_inner(mut self) -> helpers::LogService<'a> {
+        self.inner.take().expect("inner LogService is None")
+    }
+
+    /// Fetch all Log signal producers the log service is attached to.
+    pub fn subscribers(&self) -> Result<Vec<LogSubscriber>> {
+        block_on(self.inner().subscribers())
+    }
+
+    /// Attach the log service to a Log signal producer.
+    pub fn attach(&self, interface: &str) -> Result<()> {
+        block_on(self.inner().attach(interface))
+    }
+
+    /// Detach the log service from a Log signal producer.
+    pub fn detach(&self, interface: &str) -> Result<()> {
+        block_on(self.inner().detach(interface))
+    }
+
+    /// Get the log verbosity.
+    pub fn log_level(&self) -> Result<LogLevel> {
+        block_on(self.inner().log_level())
+    }
+
+    /// Set the log verbosity.
+    pub fn set_log_level(&self, value: LogLevel) -> Result<()> {
+        block_on(self.inner().set_log_level(value))
+    }
+
+    /// Get whether each log line is prefixed with a timestamp.
+    pub fn timestamp(&self) -> Result<bool> {
+        block_on(self.inner().timestamp())
+    }
+
+    /// Set whether each log line is prefixed with a timestamp.
+    pub fn set_timestamp(&self, value: bool) -> Result<()> {
+        block_on(self.inner().set_timestamp(value))
+    }
+
+    /// Get whether each Log event is preceded by details about its D-Bus sender.
+    pub fn log_dbus_details(&self) -> Result<bool> {
+        block_on(self.inner().log_dbus_details())
+    }
+
+    /// Set whether each Log event is preceded by details about its D-Bus sender.
+    pub fn set_log_dbus_details(&self, value: bool) -> Result<()> {
+        block_on(self.inner().set_log_dbus_details(value))
+    }
+
+    /// Get whether logged messages are prefixed with the sender's LogTag hash value.
+    pub fn log_prefix_logtag(&self) -> Result<bool> {
+        block_on(self.inner().log_prefix_logtag())
+    }
+
+    /// Set whether logged messages are prefixed with the sender's LogTag hash value.
+    pub fn set_log_prefix_logtag(&self, value: bool) -> Result<()> {
+        block_on(self.inner().set_log_prefix_logtag(value))
+    }
+
+    /// Get the logging method in use.
+    pub fn log_method(&self) -> Result<String> {
+        block_on(self.inner().log_method())
+    }
+
+    /// Get the number of attached subscriptions.
+    pub fn num_attached(&self) -> Result<u32> {
+        block_on(self.inner().num_attached())
+    }
+
+    /// Get version information about the running service.
+    pub fn version(&self) -> Result<String> {
+        block_on(self.inner().version())
+    }
+
+    /// Forward the Log and StatusChange events of a session to another D-Bus service.
+    ///
+    /// See [helpers::LogService::proxy_log_events].
+    pub fn proxy_log_events(
+        &self,
+        target_address: &str,
+        session_path: &ObjectPath<'_>,
+    ) -> Result<LogNodeProxyBlocking<'a>> {
+        Ok(LogNodeProxyBlocking::from(
+            block_on(self.inner().proxy_log_events(target_address, session_path))?.into_inner(),
+        ))
+    }
+}
+
+impl<'a> From<helpers::LogService<'a>> for LogService<'a> {
+    fn from(inner: helpers::LogService<'a>) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+impl Drop for LogService<'_> {
+    fn drop(&mut self) {
+        runtime::enter(|| drop(self.inner.take()));
+    }
+}
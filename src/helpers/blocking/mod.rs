@@ -0,0 +1,81 @@
+//! Blocking counterparts of the helper structs, for use outside of an async runtime.
+//!
+//! Each method blocks the calling thread until the corresponding async method of the wrapped helper completes. Streams are exposed as blocking iterators.
+//!
+//! With the `tokio` feature the wrapped helpers run on a runtime shared by all wrappers, so their methods must not be called from within another runtime. Dropping a wrapper never blocks.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use openvpn3_rs::helpers::{blocking::OpenVPN3, CredentialSet};
+//! use std::time::Duration;
+//!
+//! let openvpn3 = OpenVPN3::connect().unwrap();
+//! let config = openvpn3.import("VPN", "remote my-server 1194\n", true, false).unwrap();
+//! let session = config.new_tunnel().unwrap();
+//!
+//! session
+//!     .connect_with(&CredentialSet::new("smith", "hunter2"), Duration::from_secs(30))
+//!     .unwrap();
+//!
+//! for state in session.state_stream().unwrap() {
+//!     println!("{:?}", state);
+//! }
+//! ```
+
+mod client;
+mod configuration;
+mod log;
+mod session;
+mod statistics;
+
+pub use client::OpenVPN3;
+pub use configuration::Configuration;
+pub use log::LogService;
+pub use session::{Session, UserInputSlot};
+pub use statistics::StatisticsSampler;
+
+use crate::runtime::{self, block_on};
+
+use futures_util::{Stream, StreamExt};
+use std::{fmt, pin::Pin};
+
+/// Blocking Iterator
+///
+/// Blocks on each call to [Iterator::next] until the wrapped stream yields its next item.
+pub struct Iter<'a, T> {
+    stream: Option<Pin<Box<dyn Stream<Item = T> + Send + 'a>>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'a,
+    {
+        Self {
+            stream: Some(Box::pin(stream)),
+        }
+    }
+}
+
+impl<T> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        block_on(self.stream.as_mut()?.next())
+    }
+}
+
+impl<T> Drop for Iter<'_, T> {
+    fn drop(&mut self) {
+        // Streams and proxies remove their match rules on a background task when dropped, which needs a runtime with the
+        // `tokio` feature. The wrappers in this module therefore drop their inner values within `runtime::enter`.
+        runtime::enter(|| drop(self.stream.take()));
+    }
+}
+
+impl<T> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,344 @@
+use super::Iter;
+
+use crate::{
+    helpers::{
+        self, CredentialProvider, DynamicChallenge, LogEvent, SessionState, StaticChallenge,
+        WebAuthOpener, WebAuthRequest,
+    },
+    runtime::{self, block_on},
+    sessions_node::{
+        constants::{ClientAttentionGroup, ClientAttentionType},
+        result::{Statistics, Status, UserInputQueueTypeGroup},
+        AttentionRequired, Log, StatusChange,
+    },
+    Result,
+};
+
+use std::time::Duration;
+use zbus::{
+    blocking::Connection,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
+};
+
+/// OpenVPN 3 Session
+///
+/// Blocking counterpart of [helpers::Session].
+#[derive(Clone, Debug)]
+pub struct Session<'a> {
+    inner: Option<helpers::Session<'a>>,
+}
+
+impl<'a> Session<'a> {
+    /// Constructs a new [Session] that represents a single OpenVPN 3 VPN session through the D-Bus API.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - D-Bus [Connection] instance.
+    /// * `session_path` - D-Bus [OwnedObjectPath] to the VPN session.
+    pub fn new(conn: Connection, session_path: OwnedObjectPath) -> Result<Session<'a>> {
+        block_on(helpers::Session::new(conn.into_inner(), session_path)).map(Self::from)
+    }
+
+    /// Constructs a new [Session], reaching the session manager at a bus name other than its well-known name.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - D-Bus [Connection] instance.
+    /// * `destination` - Bus name of the session manager.
+    /// * `session_path` - D-Bus [OwnedObjectPath] to the VPN session.
+    pub fn with_destination(
+        conn: Connection,
+        destination: &str,
+        session_path: OwnedObjectPath,
+    ) -> Result<Session<'a>> {
+        block_on(helpers::Session::with_destination(
+            conn.into_inner(),
+            destination,
+            session_path,
+        ))
+        .map(Self::from)
+    }
+
+    /// Get the underlying async [helpers::Session].
+    pub fn inner(&self) -> &helpers::Session<'a> {
+        self.inner.as_ref().expect("inner Session is None")
+    }
+
+    /// Consume `self`, returning the underlying async [helpers::Session].
+    pub fn into_inner(mut self) -> helpers::Session<'a> {
+        self.inner.take().expect("inner Session is None")
+    }
+
+    /// Get a reference to the underlying proxy's object path.
+    pub fn path(&'a self) -> &'a ObjectPath<'a> {
+        self.inner().path()
+    }
+
+    /// Check whether the session is ready to connect.
+    ///
+    /// See [helpers::Session::ready].
+    pub fn ready(&'a self) -> Result<()> {
+        block_on(self.inner().ready())
+    }
+
+    /// Check whether the session uses Data Channel Offload.
+    ///
+    /// See [helpers::Session::dco].
+    pub fn dco(&self) -> Result<bool> {
+        block_on(self.inner().dco())
+    }
+
+    /// Enable or disable Data Channel Offload for this session.
+    ///
+    /// See [helpers::Session::set_dco].
+    pub fn set_dco(&self, enabled: bool) -> Result<()> {
+        block_on(self.inner().set_dco(enabled))
+    }
+
+    /// Start the connection process.
+    pub fn connect(&'a self) -> Result<()> {
+        block_on(self.inner().connect())
+    }
+
+    /// Pause an active connection.
+    pub fn pause(&'a self, reason: &str) -> Result<()> {
+        block_on(self.inner().pause(reason))
+    }
+
+    /// Resume a paused connection.
+    pub fn resume(&'a self) -> Result<()> {
+        block_on(self.inner().resume())
+    }
+
+    /// Disconnect and reconnect.
+    pub fn restart(&'a self) -> Result<()> {
+        block_on(self.inner().restart())
+    }
+
+    /// Disconnect and remove the VPN session.
+    pub fn disconnect(&'a self) -> Result<()> {
+        block_on(self.inner().disconnect())
+    }
+
+    /// Get the last processed status of the VPN session.
+    pub fn status(&'a self) -> Result<Status> {
+        block_on(self.inner().status())
+    }
+
+    /// Get the current [SessionState].
+    pub fn state(&self) -> Result<SessionState> {
+        block_on(self.inner().state())
+    }
+
+    /// Iterate over the [SessionState]s the session transitions into.
+    ///
+    /// See [helpers::Session::state_stream].
+    pub fn state_stream(&self) -> Result<Iter<'a, SessionState>> {
+        block_on(self.inner().state_stream()).map(Iter::new)
+    }
+
+    /// Wait until the session has connected.
+    ///
+    /// See [helpers::Session::wait_until_connected].
+    pub fn wait_until_connected(&self, timeout: Duration) -> Result<()> {
+        block_on(self.inner().wait_until_connected(timeout))
+    }
+
+    /// Answer all pending user input requests of the session.
+    ///
+    /// See [helpers::Session::provide_credentials].
+    pub fn provide_credentials<P>(&'a self, provider: &P) -> Result<()>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        block_on(self.inner().provide_credentials(provider))
+    }
+
+    /// Connect the session, answering user input requests as they come up.
+    ///
+    /// See [helpers::Session::connect_with].
+    pub fn connect_with<P>(&'a self, provider: &P, timeout: Duration) -> Result<()>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        block_on(self.inner().connect_with(provider, timeout))
+    }
+
+    /// Get the pending web based authentication request, if any.
+    pub fn web_auth_request(&'a self) -> Result<Option<WebAuthRequest>> {
+        block_on(self.inner().web_auth_request())
+    }
+
+    /// Iterate over the web based authentication requests of the session.
+    pub fn web_auth_stream(&self) -> Result<Iter<'a, WebAuthRequest>> {
+        block_on(self.inner().web_auth_stream()).map(Iter::new)
+    }
+
+    /// Complete web based authentication.
+    ///
+    /// See [helpers::Session::authenticate_web].
+    pub fn authenticate_web<O>(&'a self, opener: &O, timeout: Duration) -> Result<()>
+    where
+        O: WebAuthOpener + ?Sized,
+    {
+        block_on(self.inner().authenticate_web(opener, timeout))
+    }
+
+    /// Get the name of the configuration profile the session was started from.
+    pub fn config_name(&self) -> Result<String> {
+        block_on(self.inner().config_name())
+    }
+
+    /// Get the tunnel statistics.
+    pub fn statistics(&'a self) -> Result<Statistics> {
+        block_on(self.inner().statistics())
+    }
+
+    /// Get a property value from the underlying D-Bus proxy.
+    pub fn get_property<T>(&'a self, property_name: &str) -> Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        block_on(self.inner().get_property(property_name))
+    }
+
+    /// Get the type and group of all pending user input requests.
+    pub fn user_input_queue_get_type_group(&'a self) -> Result<Vec<UserInputQueueTypeGroup>> {
+        block_on(self.inner().user_input_queue_get_type_group())
+    }
+
+    /// Get the IDs of the pending user input requests of a type and group.
+    pub fn user_input_queue_check(
+        &'a self,
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+    ) -> Result<Vec<u32>> {
+        block_on(self.inner().user_input_queue_check(qtype, qgroup))
+    }
+
+    /// Fetch a [UserInputSlot] which represents a request for user input and which can be used to provide input to the backend.
+    ///
+    /// See [helpers::Session::user_input_queue_fetch].
+    pub fn user_input_queue_fetch(
+        &'a self,
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        qid: u32,
+    ) -> Result<UserInputSlot<'a>> {
+        block_on(self.inner().user_input_queue_fetch(qtype, qgroup, qid)).map(UserInputSlot::from)
+    }
+
+    /// Fetch all required user inputs.
+    pub fn fetch_user_input_slots(&'a self) -> Result<Vec<UserInputSlot<'a>>> {
+        Ok(block_on(self.inner().fetch_user_input_slots())?
+            .into_iter()
+            .map(UserInputSlot::from)
+            .collect())
+    }
+
+    /// Iterate over the AttentionRequired signals of this VPN session.
+    pub fn attention_required_stream(&self) -> Result<Iter<'a, AttentionRequired>> {
+        block_on(self.inner().attention_required_stream()).map(Iter::new)
+    }
+
+    /// Iterate over the StatusChange signals of this VPN session.
+    pub fn status_change_stream(&self) -> Result<Iter<'a, StatusChange>> {
+        block_on(self.inner().status_change_stream()).map(Iter::new)
+    }
+
+    /// Iterate over the Log signals of this VPN session.
+    ///
+    /// This should be called after the backend process is ready
+    pub fn log_stream(&self) -> Result<Iter<'a, Log>> {
+        block_on(self.inner().log_stream()).map(Iter::new)
+    }
+
+    /// Iterate over the [LogEvent]s of this VPN session.
+    ///
+    /// Log signals which cannot be parsed are skipped. This should be called after the backend process is ready.
+    pub fn log_events(&self) -> Result<Iter<'a, LogEvent>> {
+        block_on(self.inner().log_events()).map(Iter::new)
+    }
+}
+
+impl<'a> From<helpers::Session<'a>> for Session<'a> {
+    fn from(inner: helpers::Session<'a>) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        runtime::enter(|| drop(self.inner.take()));
+    }
+}
+
+/// User Input Slot
+///
+/// Blocking counterpart of [helpers::UserInputSlot].
+pub struct UserInputSlot<'a> {
+    inner: Option<helpers::UserInputSlot<'a>>,
+}
+
+impl<'a> UserInputSlot<'a> {
+    /// Get the underlying async [helpers::UserInputSlot].
+    pub fn inner(&self) -> &helpers::UserInputSlot<'a> {
+        self.inner.as_ref().expect("inner UserInputSlot is None")
+    }
+
+    /// Provide input to the backend for this request.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Input value.
+    pub fn provide_input(&'a self, value: &str) -> Result<()> {
+        block_on(self.inner().provide_input(value))
+    }
+
+    /// A tuple consisting of this request's [ClientAttentionType] and [ClientAttentionGroup].
+    pub fn type_group(&'a self) -> UserInputQueueTypeGroup {
+        self.inner().type_group()
+    }
+
+    /// Internal variable name.
+    pub fn variable_name(&'a self) -> &'a str {
+        self.inner().variable_name()
+    }
+
+    /// A description to present to the user.
+    pub fn label(&'a self) -> &'a str {
+        self.inner().label()
+    }
+
+    /// Should the user's input be masked/hidden?
+    pub fn input_mask(&'a self) -> bool {
+        self.inner().input_mask()
+    }
+
+    /// Parse this request as a [DynamicChallenge].
+    ///
+    /// Returns `Ok(None)` if this is not a [ClientAttentionGroup::ChallengeDynamic] request.
+    pub fn dynamic_challenge(&self) -> Result<Option<DynamicChallenge>> {
+        self.inner().dynamic_challenge()
+    }
+
+    /// Get this request as a [StaticChallenge].
+    ///
+    /// Returns `None` if this is not a [ClientAttentionGroup::ChallengeStatic] request.
+    pub fn static_challenge(&self) -> Option<StaticChallenge> {
+        self.inner().static_challenge()
+    }
+}
+
+impl<'a> From<helpers::UserInputSlot<'a>> for UserInputSlot<'a> {
+    fn from(inner: helpers::UserInputSlot<'a>) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+impl Drop for UserInputSlot<'_> {
+    fn drop(&mut self) {
+        runtime::enter(|| drop(self.inner.take()));
+    }
+}
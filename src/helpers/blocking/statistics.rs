@@ -0,0 +1,51 @@
+use super::{Iter, Session};
+
+use crate::{
+    helpers::{self, StatisticsSample},
+    runtime::block_on,
+    Result,
+};
+
+use std::time::Duration;
+
+/// Statistics Sampler
+///
+/// Blocking counterpart of [helpers::StatisticsSampler].
+#[derive(Clone, Debug)]
+pub struct StatisticsSampler<'a> {
+    inner: helpers::StatisticsSampler<'a>,
+}
+
+impl<'a> StatisticsSampler<'a> {
+    /// Create a new [StatisticsSampler].
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - Session to sample the statistics of.
+    /// * `interval` - Time between samples yielded by the iterator.
+    pub fn new(session: &'a Session<'a>, interval: Duration) -> Self {
+        Self {
+            inner: helpers::StatisticsSampler::new(session.inner(), interval),
+        }
+    }
+
+    /// Get the time between samples.
+    pub fn interval(&self) -> Duration {
+        self.inner.interval()
+    }
+
+    /// Fetch the statistics now, computing rates since the previous sample.
+    pub fn sample(&mut self) -> Result<StatisticsSample> {
+        block_on(self.inner.sample())
+    }
+}
+
+impl<'a> IntoIterator for StatisticsSampler<'a> {
+    type Item = Result<StatisticsSample>;
+    type IntoIter = Iter<'a, Result<StatisticsSample>>;
+
+    /// Take a sample immediately and then once every interval.
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.inner.into_stream())
+    }
+}
@@ -0,0 +1,349 @@
+//! Forwards OpenVPN 3 Log signals into the `tracing` and `log` ecosystems.
+//!
+//! Requires the `tracing` and/or `log` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # #[cfg(feature = "tracing")]
+//! # async_std::task::block_on(async {
+//! use openvpn3_rs::helpers::{forward_to_tracing, OpenVPN3};
+//!
+//! let openvpn3 = OpenVPN3::connect().await.unwrap();
+//! forward_to_tracing(&openvpn3, openvpn3.all_logs().await.unwrap()).await;
+//! # });
+//! ```
+
+use super::{LogEvent, LogSource, OpenVPN3, Service, Session};
+
+use crate::{
+    log::constants::{LogCategory, LogLevel},
+    sessions::constants::EventType,
+};
+
+use futures_util::{future, stream, Stream, StreamExt};
+use std::collections::HashMap;
+use zbus::zvariant::OwnedObjectPath;
+
+/// Target used for all forwarded events.
+const TARGET: &str = "openvpn3";
+
+/// Map a [LogCategory] to the `tracing` level it is emitted at.
+#[cfg(feature = "tracing")]
+fn tracing_level(category: LogCategory) -> tracing::Level {
+    use tracing::Level;
+
+    match category {
+        LogCategory::FATAL | LogCategory::CRIT | LogCategory::ERROR => Level::ERROR,
+        LogCategory::WARN => Level::WARN,
+        LogCategory::INFO | LogCategory::UNDEFINED => Level::INFO,
+        LogCategory::VERB1 => Level::DEBUG,
+        LogCategory::VERB2 | LogCategory::DEBUG => Level::TRACE,
+    }
+}
+
+/// Map a [LogCategory] to the `log` level it is emitted at.
+#[cfg(feature = "log")]
+fn log_level(category: LogCategory) -> ::log::Level {
+    use ::log::Level;
+
+    match category {
+        LogCategory::FATAL | LogCategory::CRIT | LogCategory::ERROR => Level::Error,
+        LogCategory::WARN => Level::Warn,
+        LogCategory::INFO | LogCategory::UNDEFINED => Level::Info,
+        LogCategory::VERB1 => Level::Debug,
+        LogCategory::VERB2 | LogCategory::DEBUG => Level::Trace,
+    }
+}
+
+/// The most verbose `tracing` level a [LogLevel] lets through, matching the levels [LogCategory]s are emitted at.
+///
+/// This allows the verbosity of a session or the log service to be configured from the filter of a `tracing` subscriber, or the other way around.
+#[cfg(feature = "tracing")]
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::FATAL | LogLevel::ERROR => Self::ERROR,
+            LogLevel::WARNING => Self::WARN,
+            LogLevel::INFO => Self::INFO,
+            LogLevel::VERB1 => Self::DEBUG,
+            LogLevel::VERB2 | LogLevel::DEBUG => Self::TRACE,
+        }
+    }
+}
+
+/// The most verbose `log` level a [LogLevel] lets through, matching the levels [LogCategory]s are emitted at.
+#[cfg(feature = "log")]
+impl From<LogLevel> for ::log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::FATAL | LogLevel::ERROR => Self::Error,
+            LogLevel::WARNING => Self::Warn,
+            LogLevel::INFO => Self::Info,
+            LogLevel::VERB1 => Self::Debug,
+            LogLevel::VERB2 | LogLevel::DEBUG => Self::Trace,
+        }
+    }
+}
+
+impl LogEvent {
+    /// Emit this event as a `tracing` event.
+    ///
+    /// The event is emitted inside an INFO span carrying the session path, log group, source and configuration profile name.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - Name of the configuration profile the event relates to, if known.
+    #[cfg(feature = "tracing")]
+    pub fn emit_tracing(&self, config_name: Option<&str>) {
+        use tracing::Level;
+
+        let session = match &self.source {
+            LogSource::Session(path) => Some(path.as_str()),
+            _ => None,
+        };
+
+        let span = tracing::span!(
+            target: TARGET,
+            Level::INFO,
+            "openvpn3",
+            session = session,
+            group = %self.group,
+            source = %self.source,
+            config_name = config_name,
+        );
+        let _entered = span.enter();
+
+        // `tracing` needs the level of an event at compile time
+        match tracing_level(self.severity) {
+            Level::ERROR => tracing::event!(target: TARGET, Level::ERROR, "{}", self.message),
+            Level::WARN => tracing::event!(target: TARGET, Level::WARN, "{}", self.message),
+            Level::INFO => tracing::event!(target: TARGET, Level::INFO, "{}", self.message),
+            Level::DEBUG => tracing::event!(target: TARGET, Level::DEBUG, "{}", self.message),
+            _ => tracing::event!(target: TARGET, Level::TRACE, "{}", self.message),
+        }
+    }
+
+    /// Emit this event as a `log` record.
+    ///
+    /// The session path (or other source), log group and configuration profile name are prepended to the message.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - Name of the configuration profile the event relates to, if known.
+    #[cfg(feature = "log")]
+    pub fn emit_log(&self, config_name: Option<&str>) {
+        let level = log_level(self.severity);
+
+        match config_name {
+            Some(config_name) => ::log::log!(
+                target: TARGET,
+                level,
+                "[{}] [{}] {}: {}",
+                self.source,
+                config_name,
+                self.group,
+                self.message
+            ),
+            None => ::log::log!(
+                target: TARGET,
+                level,
+                "[{}] {}: {}",
+                self.source,
+                self.group,
+                self.message
+            ),
+        }
+    }
+}
+
+/// Configuration profile names of sessions, looked up once per session.
+struct ConfigNames<'o, 'a> {
+    openvpn3: &'o OpenVPN3<'a>,
+    names: HashMap<OwnedObjectPath, String>,
+}
+
+impl<'o, 'a> ConfigNames<'o, 'a> {
+    fn new(openvpn3: &'o OpenVPN3<'a>) -> Self {
+        Self {
+            openvpn3,
+            names: HashMap::new(),
+        }
+    }
+
+    /// Get the name of the configuration profile of the session an event was sent by.
+    ///
+    /// Failed lookups are not cached, so they are retried with the next event of the session.
+    async fn get(&mut self, event: &LogEvent) -> Option<&str> {
+        let path = match &event.source {
+            LogSource::Session(path) => path,
+            _ => return None,
+        };
+
+        if !self.names.contains_key(path) {
+            let session = Session::with_destination(
+                self.openvpn3.connection().clone(),
+                self.openvpn3.destination(Service::Sessions),
+                path.clone(),
+            )
+            .await
+            .ok()?;
+            let name = session.config_name().await.ok()?;
+            self.names.insert(path.clone(), name);
+        }
+
+        self.names.get(path).map(String::as_str)
+    }
+
+    /// Forget the name of a session which was removed.
+    fn remove(&mut self, path: &OwnedObjectPath) {
+        self.names.remove(path);
+    }
+}
+
+enum Forwarded {
+    Event(LogEvent),
+    SessionRemoved(OwnedObjectPath),
+    End,
+}
+
+/// Emit every [LogEvent] of a stream until it ends, evicting the cached name of a session once the session manager reports it removed.
+async fn forward<S, F>(openvpn3: &OpenVPN3<'_>, stream: S, emit: F)
+where
+    S: Stream<Item = LogEvent>,
+    F: Fn(&LogEvent, Option<&str>),
+{
+    let mut names = ConfigNames::new(openvpn3);
+
+    let removed = match openvpn3.event_stream().await {
+        Ok(events) => events
+            .filter_map(|signal| {
+                future::ready(signal.args().ok().and_then(|args| {
+                    (args.event_type == EventType::SessDestroyed)
+                        .then(|| Forwarded::SessionRemoved(args.path.into()))
+                }))
+            })
+            .left_stream(),
+        Err(_) => stream::empty().right_stream(),
+    };
+    let events = stream
+        .map(Forwarded::Event)
+        .chain(stream::once(future::ready(Forwarded::End)));
+    let forwarded = stream::select(events, removed);
+    futures_util::pin_mut!(forwarded);
+
+    while let Some(forwarded) = forwarded.next().await {
+        match forwarded {
+            Forwarded::Event(event) => emit(&event, names.get(&event).await),
+            Forwarded::SessionRemoved(path) => names.remove(&path),
+            Forwarded::End => break,
+        }
+    }
+}
+
+/// Emit every [LogEvent] of a stream as a `tracing` event, until the stream ends.
+///
+/// The configuration profile name of each session is looked up through `openvpn3` and cached until the session is removed.
+///
+/// # Arguments
+///
+/// * `openvpn3` - Client used to look up the configuration profile names of sessions.
+/// * `stream` - Stream of [LogEvent]s, e.g. from [OpenVPN3::all_logs] or [Session::log_events].
+#[cfg(feature = "tracing")]
+pub async fn forward_to_tracing<S>(openvpn3: &OpenVPN3<'_>, stream: S)
+where
+    S: Stream<Item = LogEvent>,
+{
+    forward(openvpn3, stream, LogEvent::emit_tracing).await
+}
+
+/// Emit every [LogEvent] of a stream as a `log` record, until the stream ends.
+///
+/// The configuration profile name of each session is looked up through `openvpn3` and cached until the session is removed.
+///
+/// # Arguments
+///
+/// * `openvpn3` - Client used to look up the configuration profile names of sessions.
+/// * `stream` - Stream of [LogEvent]s, e.g. from [OpenVPN3::all_logs] or [Session::log_events].
+#[cfg(feature = "log")]
+pub async fn forward_to_log<S>(openvpn3: &OpenVPN3<'_>, stream: S)
+where
+    S: Stream<Item = LogEvent>,
+{
+    forward(openvpn3, stream, LogEvent::emit_log).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_levels() {
+        use tracing::Level;
+
+        assert_eq!(tracing_level(LogCategory::FATAL), Level::ERROR);
+        assert_eq!(tracing_level(LogCategory::CRIT), Level::ERROR);
+        assert_eq!(tracing_level(LogCategory::ERROR), Level::ERROR);
+        assert_eq!(tracing_level(LogCategory::WARN), Level::WARN);
+        assert_eq!(tracing_level(LogCategory::INFO), Level::INFO);
+        assert_eq!(tracing_level(LogCategory::UNDEFINED), Level::INFO);
+        assert_eq!(tracing_level(LogCategory::VERB1), Level::DEBUG);
+        assert_eq!(tracing_level(LogCategory::VERB2), Level::TRACE);
+        assert_eq!(tracing_level(LogCategory::DEBUG), Level::TRACE);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_filters() {
+        use tracing::level_filters::LevelFilter;
+
+        assert_eq!(LevelFilter::from(LogLevel::FATAL), LevelFilter::ERROR);
+        assert_eq!(LevelFilter::from(LogLevel::ERROR), LevelFilter::ERROR);
+        assert_eq!(LevelFilter::from(LogLevel::WARNING), LevelFilter::WARN);
+        assert_eq!(LevelFilter::from(LogLevel::INFO), LevelFilter::INFO);
+        assert_eq!(LevelFilter::from(LogLevel::VERB1), LevelFilter::DEBUG);
+        assert_eq!(LevelFilter::from(LogLevel::VERB2), LevelFilter::TRACE);
+        assert_eq!(LevelFilter::from(LogLevel::DEBUG), LevelFilter::TRACE);
+
+        // every category a level lets through is emitted at a level the filter enables
+        for (level, category) in [
+            (LogLevel::WARNING, LogCategory::WARN),
+            (LogLevel::INFO, LogCategory::INFO),
+            (LogLevel::VERB1, LogCategory::VERB1),
+            (LogLevel::DEBUG, LogCategory::DEBUG),
+        ] {
+            assert!(LevelFilter::from(level) >= tracing_level(category));
+        }
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn log_levels() {
+        use ::log::Level;
+
+        assert_eq!(log_level(LogCategory::FATAL), Level::Error);
+        assert_eq!(log_level(LogCategory::CRIT), Level::Error);
+        assert_eq!(log_level(LogCategory::ERROR), Level::Error);
+        assert_eq!(log_level(LogCategory::WARN), Level::Warn);
+        assert_eq!(log_level(LogCategory::INFO), Level::Info);
+        assert_eq!(log_level(LogCategory::UNDEFINED), Level::Info);
+        assert_eq!(log_level(LogCategory::VERB1), Level::Debug);
+        assert_eq!(log_level(LogCategory::VERB2), Level::Trace);
+        assert_eq!(log_level(LogCategory::DEBUG), Level::Trace);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn log_filters() {
+        use ::log::LevelFilter;
+
+        assert_eq!(LevelFilter::from(LogLevel::FATAL), LevelFilter::Error);
+        assert_eq!(LevelFilter::from(LogLevel::ERROR), LevelFilter::Error);
+        assert_eq!(LevelFilter::from(LogLevel::WARNING), LevelFilter::Warn);
+        assert_eq!(LevelFilter::from(LogLevel::INFO), LevelFilter::Info);
+        assert_eq!(LevelFilter::from(LogLevel::VERB1), LevelFilter::Debug);
+        assert_eq!(LevelFilter::from(LogLevel::VERB2), LevelFilter::Trace);
+        assert_eq!(LevelFilter::from(LogLevel::DEBUG), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,238 @@
+//! Configures how an [OpenVPN3] client connects to the OpenVPN 3 services.
+
+use super::OpenVPN3;
+
+use crate::{runtime, Result};
+
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use zbus::{Connection, ConnectionBuilder};
+
+/// OpenVPN 3 D-Bus Service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Service {
+    /// Session manager (`net.openvpn.v3.sessions`).
+    Sessions,
+    /// Configuration manager (`net.openvpn.v3.configuration`).
+    Configuration,
+    /// Log service (`net.openvpn.v3.log`).
+    Log,
+    /// Network configuration service (`net.openvpn.v3.netcfg`).
+    NetCfg,
+}
+
+impl Service {
+    /// Well-known bus name the service is available under on the system bus.
+    pub fn default_destination(&self) -> &'static str {
+        match self {
+            Self::Sessions => "net.openvpn.v3.sessions",
+            Self::Configuration => "net.openvpn.v3.configuration",
+            Self::Log => "net.openvpn.v3.log",
+            Self::NetCfg => "net.openvpn.v3.netcfg",
+        }
+    }
+
+    /// D-Bus interface implemented by the service's main object.
+    pub fn interface(&self) -> &'static str {
+        self.default_destination()
+    }
+
+    /// D-Bus object path of the service's main object.
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Sessions => "/net/openvpn/v3/sessions",
+            Self::Configuration => "/net/openvpn/v3/configuration",
+            Self::Log => "/net/openvpn/v3/log",
+            Self::NetCfg => "/net/openvpn/v3/netcfg",
+        }
+    }
+
+    /// All OpenVPN 3 services.
+    pub fn all() -> [Service; 4] {
+        [Self::Sessions, Self::Configuration, Self::Log, Self::NetCfg]
+    }
+}
+
+impl fmt::Display for Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.default_destination())
+    }
+}
+
+/// Bus names the OpenVPN 3 services are reached at.
+///
+/// Also caches what the services support, shared by the client and every object created through it.
+#[derive(Clone, Debug)]
+pub(crate) struct Destinations {
+    sessions: String,
+    configuration: String,
+    log: String,
+    netcfg: String,
+    dco: Arc<OnceLock<bool>>,
+}
+
+impl Destinations {
+    pub(crate) fn get(&self, service: Service) -> &str {
+        match service {
+            Service::Sessions => &self.sessions,
+            Service::Configuration => &self.configuration,
+            Service::Log => &self.log,
+            Service::NetCfg => &self.netcfg,
+        }
+    }
+
+    pub(crate) fn set(&mut self, service: Service, destination: &str) {
+        let field = match service {
+            Service::Sessions => &mut self.sessions,
+            Service::Configuration => &mut self.configuration,
+            Service::Log => &mut self.log,
+            Service::NetCfg => &mut self.netcfg,
+        };
+
+        *field = destination.to_string();
+        self.dco = Arc::default();
+    }
+
+    /// Whether the services support Data Channel Offload, if already known.
+    pub(crate) fn dco(&self) -> Option<bool> {
+        self.dco.get().copied()
+    }
+
+    pub(crate) fn set_dco(&self, supported: bool) {
+        let _ = self.dco.set(supported);
+    }
+}
+
+impl Default for Destinations {
+    fn default() -> Self {
+        Self {
+            sessions: Service::Sessions.default_destination().to_string(),
+            configuration: Service::Configuration.default_destination().to_string(),
+            log: Service::Log.default_destination().to_string(),
+            netcfg: Service::NetCfg.default_destination().to_string(),
+            dco: Arc::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Bus {
+    System,
+    Session,
+    Address(String),
+    Connection(Connection),
+}
+
+/// OpenVPN 3 Client Builder
+///
+/// Builds an [OpenVPN3] client. By default the client connects to the system bus and uses the well-known bus names of the OpenVPN 3 services.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async_std::task::block_on(async {
+/// use openvpn3_rs::helpers::{OpenVPN3, Service};
+/// use std::time::Duration;
+///
+/// let openvpn3 = OpenVPN3::builder()
+///     .address("unix:path=/run/container/dbus/system_bus_socket")
+///     .destination(Service::Sessions, "net.openvpn.v3.sessions")
+///     .timeout(Duration::from_secs(5))
+///     .build()
+///     .await
+///     .unwrap();
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct OpenVPN3Builder {
+    bus: Bus,
+    destinations: Destinations,
+    timeout: Option<Duration>,
+}
+
+impl OpenVPN3Builder {
+    pub(crate) fn new() -> Self {
+        Self {
+            bus: Bus::System,
+            destinations: Destinations::default(),
+            timeout: None,
+        }
+    }
+
+    /// Connect to the system bus. This is the default.
+    pub fn system(mut self) -> Self {
+        self.bus = Bus::System;
+        self
+    }
+
+    /// Connect to the session bus.
+    pub fn session(mut self) -> Self {
+        self.bus = Bus::Session;
+        self
+    }
+
+    /// Connect to the bus at a D-Bus address, e.g. `unix:path=/run/dbus/system_bus_socket`.
+    pub fn address(mut self, address: &str) -> Self {
+        self.bus = Bus::Address(address.to_string());
+        self
+    }
+
+    /// Use an existing D-Bus [Connection].
+    pub fn connection(mut self, connection: Connection) -> Self {
+        self.bus = Bus::Connection(connection);
+        self
+    }
+
+    /// Reach a service at a bus name other than its well-known name.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The OpenVPN 3 [Service].
+    /// * `destination` - Bus name the service is available under.
+    pub fn destination(mut self, service: Service, destination: &str) -> Self {
+        self.destinations.set(service, destination);
+        self
+    }
+
+    /// Set the maximum time to wait for the bus connection and the service proxies to be set up.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Connect to the bus and construct the [OpenVPN3] client.
+    ///
+    /// # Returns
+    ///
+    /// [crate::Error::Timeout] if a timeout was set and the client could not be set up in time.
+    pub async fn build<'a>(self) -> Result<OpenVPN3<'a>> {
+        let Self {
+            bus,
+            destinations,
+            timeout,
+        } = self;
+
+        let build = async move {
+            let connection = match bus {
+                Bus::System => Connection::system().await?,
+                Bus::Session => Connection::session().await?,
+                Bus::Address(address) => {
+                    ConnectionBuilder::address(address.as_str())?
+                        .build()
+                        .await?
+                }
+                Bus::Connection(connection) => connection,
+            };
+
+            OpenVPN3::with_destinations(connection, destinations).await
+        };
+
+        match timeout {
+            Some(timeout) => runtime::timeout(timeout, build).await?,
+            None => build.await,
+        }
+    }
+}
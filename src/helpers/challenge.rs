@@ -0,0 +1,244 @@
+//! Challenge/response authentication requested by the VPN server.
+
+use super::UserInputSlot;
+
+use crate::{
+    profile::parse_directives, sessions_node::constants::ClientAttentionGroup, Error, Result,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Dynamic Challenge
+///
+/// A challenge sent by the server during authentication ([ClientAttentionGroup::ChallengeDynamic]), typically for multi-factor authentication.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::DynamicChallenge;
+///
+/// let challenge = DynamicChallenge::parse("CRV1:R,E:Om01u7Fh4LrGBS7uh0SWmzwabUiGiW6l:c21pdGg=:Enter your OTP code").unwrap();
+///
+/// assert!(challenge.echo());
+/// assert!(challenge.response_required());
+/// assert_eq!(challenge.username(), "smith");
+/// assert_eq!(challenge.challenge(), "Enter your OTP code");
+/// assert_eq!(challenge.response("123456"), "CRV1::Om01u7Fh4LrGBS7uh0SWmzwabUiGiW6l::123456");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynamicChallenge {
+    echo: bool,
+    response_required: bool,
+    state_id: Option<String>,
+    username: String,
+    challenge: String,
+}
+
+impl DynamicChallenge {
+    const PREFIX: &'static str = "CRV1:";
+
+    /// Parse a challenge in the `CRV1:<flags>:<state_id>:<base64_username>:<challenge_text>` format.
+    ///
+    /// # Arguments
+    ///
+    /// * `crv1` - Challenge string sent by the server.
+    pub fn parse(crv1: &str) -> Result<Self> {
+        let fields = crv1
+            .strip_prefix(Self::PREFIX)
+            .ok_or_else(|| Error::InvalidChallenge(String::from("missing CRV1 prefix")))?;
+
+        let mut fields = fields.splitn(4, ':');
+        let (flags, state_id, username, challenge) =
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(flags), Some(state_id), Some(username), Some(challenge)) => {
+                    (flags, state_id, username, challenge)
+                }
+                _ => {
+                    return Err(Error::InvalidChallenge(String::from(
+                        "expected flags, state ID, username and challenge text",
+                    )))
+                }
+            };
+
+        let username = STANDARD
+            .decode(username)
+            .ok()
+            .and_then(|username| String::from_utf8(username).ok())
+            .ok_or_else(|| Error::InvalidChallenge(String::from("invalid username encoding")))?;
+
+        let flags: Vec<&str> = flags.split(',').map(str::trim).collect();
+
+        Ok(Self {
+            echo: flags.contains(&"E"),
+            response_required: flags.contains(&"R"),
+            state_id: Some(state_id.to_string()),
+            username,
+            challenge: challenge.to_string(),
+        })
+    }
+
+    /// Construct a [DynamicChallenge] from a [UserInputSlot].
+    ///
+    /// If the slot's label holds a raw `CRV1` string it is parsed, otherwise the backend has already decoded the challenge and the label is used as the challenge text.
+    ///
+    /// Returns `Ok(None)` if the slot is not a [ClientAttentionGroup::ChallengeDynamic] request.
+    pub fn from_slot(slot: &UserInputSlot<'_>) -> Result<Option<Self>> {
+        if slot.type_group().1 != ClientAttentionGroup::ChallengeDynamic {
+            return Ok(None);
+        }
+
+        if slot.label().starts_with(Self::PREFIX) {
+            return Self::parse(slot.label()).map(Some);
+        }
+
+        Ok(Some(Self {
+            echo: !slot.input_mask(),
+            response_required: true,
+            state_id: None,
+            username: String::new(),
+            challenge: slot.label().to_string(),
+        }))
+    }
+
+    /// Should the user's response be echoed while typing?
+    pub fn echo(&self) -> bool {
+        self.echo
+    }
+
+    /// Does the server require a response?
+    pub fn response_required(&self) -> bool {
+        self.response_required
+    }
+
+    /// Opaque state ID which ties the response to this challenge, if the challenge was sent in the `CRV1` format.
+    pub fn state_id(&self) -> Option<&str> {
+        self.state_id.as_deref()
+    }
+
+    /// Username the challenge was issued for.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Challenge text to present to the user.
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+
+    /// Build the value to provide to the backend.
+    ///
+    /// Challenges sent in the `CRV1` format are answered in the `CRV1::<state_id>::<response>` format, otherwise the response is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The user's response to the challenge.
+    pub fn response(&self, response: &str) -> String {
+        match &self.state_id {
+            Some(state_id) => format!("CRV1::{}::{}", state_id, response),
+            None => response.to_string(),
+        }
+    }
+}
+
+/// Static Challenge
+///
+/// A challenge defined by the `static-challenge` directive of the configuration profile ([ClientAttentionGroup::ChallengeStatic]), typically asking for an OTP code before connecting.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::StaticChallenge;
+///
+/// let challenge = StaticChallenge::from_config("remote my-server 1194\nstatic-challenge \"Enter PIN\" 1\n").unwrap();
+///
+/// assert_eq!(challenge.prompt(), "Enter PIN");
+/// assert!(challenge.echo());
+///
+/// let challenge = StaticChallenge::from_config("static-challenge \"Enter \\\"the\\\" PIN\" 0\n").unwrap();
+///
+/// assert_eq!(challenge.prompt(), "Enter \"the\" PIN");
+/// assert!(!challenge.echo());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaticChallenge {
+    prompt: String,
+    echo: bool,
+}
+
+impl StaticChallenge {
+    const DIRECTIVE: &'static str = "static-challenge";
+
+    /// Construct a [StaticChallenge].
+    ///
+    /// # Arguments
+    ///
+    /// * `prompt` - Challenge text to present to the user.
+    /// * `echo` - Should the user's response be echoed while typing?
+    pub fn new(prompt: &str, echo: bool) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            echo,
+        }
+    }
+
+    /// Find the `static-challenge` directive in a configuration profile.
+    ///
+    /// The profile is read with [crate::profile::parse_directives], so quoted and escaped prompts are handled the same way as by [crate::profile::Profile].
+    ///
+    /// Returns `None` if the profile does not define a static challenge or cannot be parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_str` - Content of the configuration profile.
+    pub fn from_config(config_str: &str) -> Option<Self> {
+        parse_directives(config_str)
+            .ok()?
+            .into_iter()
+            .find(|(_, directive)| directive.name == Self::DIRECTIVE && directive.inline.is_none())
+            .and_then(|(_, directive)| {
+                let prompt = directive.args.first()?;
+                Some(Self::new(
+                    prompt,
+                    directive.args.get(1).map(String::as_str) == Some("1"),
+                ))
+            })
+    }
+
+    /// Construct a [StaticChallenge] from a [UserInputSlot].
+    ///
+    /// Returns `None` if the slot is not a [ClientAttentionGroup::ChallengeStatic] request.
+    pub fn from_slot(slot: &UserInputSlot<'_>) -> Option<Self> {
+        match slot.type_group().1 {
+            ClientAttentionGroup::ChallengeStatic => {
+                Some(Self::new(slot.label(), !slot.input_mask()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Challenge text to present to the user.
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Should the user's response be echoed while typing?
+    pub fn echo(&self) -> bool {
+        self.echo
+    }
+
+    /// Combine a password and challenge response into the `SCRV1:<base64_password>:<base64_response>` format.
+    ///
+    /// This is only needed by servers expecting the combined value as the password, OpenVPN 3 combines the values itself when they are provided separately.
+    ///
+    /// # Arguments
+    ///
+    /// * `password` - The user's password.
+    /// * `response` - The user's response to the challenge.
+    pub fn combine(password: &str, response: &str) -> String {
+        format!(
+            "SCRV1:{}:{}",
+            STANDARD.encode(password),
+            STANDARD.encode(response)
+        )
+    }
+}
@@ -1,12 +1,26 @@
-use super::{Configuration, Session};
+use super::{
+    builder::{Destinations, OpenVPN3Builder},
+    Configuration, LogEvent, LogService, Service, ServiceVersion, ServiceVersions, Session,
+};
 
 use crate::{
+    profile::Profile,
+    runtime,
     sessions::{LogStream, SessionManagerEventStream},
-    ConfigurationProxy, NetCfgProxy, Result, SessionsProxy,
+    ConfigurationProxy, Error, NetCfgProxy, Result, SessionsProxy,
 };
 
-use futures_util::future;
-use zbus::{fdo::PeerProxy, zvariant::OwnedObjectPath, Connection};
+use futures_util::{future, stream, Stream, StreamExt};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+use zbus::{
+    fdo::{DBusProxy, PeerProxy, PropertiesProxy},
+    names::{InterfaceName, WellKnownName},
+    zvariant::OwnedObjectPath,
+    Connection, MatchRule, MessageStream, MessageType,
+};
 
 /// OpenVPN 3 Client
 ///
@@ -14,30 +28,65 @@ use zbus::{fdo::PeerProxy, zvariant::OwnedObjectPath, Connection};
 #[derive(Clone, Debug)]
 pub struct OpenVPN3<'a> {
     connection: Connection,
-    peer_proxy: PeerProxy<'a>,
     sessions_proxy: SessionsProxy<'a>,
     configuration_manager_proxy: ConfigurationProxy<'a>,
+    destinations: Destinations,
 }
 impl<'a> OpenVPN3<'a> {
+    const PING_TIMEOUT: Duration = Duration::from_secs(2);
+    const BACKOFF_INITIAL: Duration = Duration::from_millis(50);
+    const BACKOFF_MAX: Duration = Duration::from_secs(1);
+
     /// Create a new `OpenVPN3` instance.
     pub async fn connect() -> Result<OpenVPN3<'a>> {
-        let connection = Connection::system().await?;
-        let sessions_proxy = SessionsProxy::new(&connection).await?;
-        let peer_proxy = PeerProxy::builder(&connection)
-            .destination("net.openvpn.v3.sessions")?
-            .path(sessions_proxy.path().to_owned())?
+        Self::new(Connection::system().await?).await
+    }
+
+    /// Create a new `OpenVPN3` instance using an existing D-Bus [Connection].
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - D-Bus [Connection] to the bus the OpenVPN 3 services are available on.
+    pub async fn new(connection: Connection) -> Result<OpenVPN3<'a>> {
+        Self::with_destinations(connection, Destinations::default()).await
+    }
+
+    /// Get an [OpenVPN3Builder] to configure the bus connection and service destinations.
+    pub fn builder() -> OpenVPN3Builder {
+        OpenVPN3Builder::new()
+    }
+
+    pub(crate) async fn with_destinations(
+        connection: Connection,
+        destinations: Destinations,
+    ) -> Result<OpenVPN3<'a>> {
+        let sessions_proxy = SessionsProxy::builder(&connection)
+            .destination(destinations.get(Service::Sessions).to_owned())?
+            .build()
+            .await?;
+        let configuration_manager_proxy = ConfigurationProxy::builder(&connection)
+            .destination(destinations.get(Service::Configuration).to_owned())?
             .build()
             .await?;
-        let configuration_manager_proxy = ConfigurationProxy::new(&connection).await?;
 
         Ok(OpenVPN3 {
             connection,
-            peer_proxy,
             sessions_proxy,
             configuration_manager_proxy,
+            destinations,
         })
     }
 
+    /// Get the underlying D-Bus [Connection].
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Bus name a service is reached at.
+    pub(crate) fn destination(&self, service: Service) -> &str {
+        self.destinations.get(service)
+    }
+
     /// Fetch all available configuration profiles that are available to the user.
     pub async fn configurations(&'a self) -> Result<Vec<Configuration<'a>>> {
         let configs = self
@@ -46,8 +95,9 @@ impl<'a> OpenVPN3<'a> {
             .await?;
 
         futures_util::future::join_all(configs.into_iter().map(|object_path| {
-            Configuration::new(
+            Configuration::with_destinations(
                 self.connection.clone(),
+                &self.destinations,
                 /*&self.sessions_proxy,*/
                 /*&self.configuration_manager_proxy,*/
                 object_path,
@@ -66,18 +116,19 @@ impl<'a> OpenVPN3<'a> {
         single_use: bool,
         persistent: bool,
     ) -> Result<Configuration<'c>> {
-        self.ping().await?;
+        self.ping(Service::Configuration).await?;
 
         let proxy = self
             .configuration_manager_proxy
             .import(name, config_str, single_use, persistent)
             .await?;
 
-        Ok(Configuration::new(
+        Configuration::with_destinations(
             self.connection.clone(),
+            &self.destinations,
             OwnedObjectPath::from(proxy.path().clone()),
         )
-        .await?)
+        .await
 
         /* Ok(Configuration {
             conn: self.connection.clone(),
@@ -88,15 +139,50 @@ impl<'a> OpenVPN3<'a> {
         }) */
     }
 
+    /// Import a parsed configuration profile.
+    ///
+    /// See [OpenVPN3::import].
+    pub async fn import_profile<'c>(
+        &self,
+        name: &str,
+        profile: &Profile,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<Configuration<'c>> {
+        self.import(name, &profile.to_string(), single_use, persistent)
+            .await
+    }
+
+    /// Import a configuration profile from a file, named after the file without its extension.
+    ///
+    /// Files referenced by the profile, e.g. `ca ca.crt` or `auth-user-pass creds.txt`, are embedded inline first, see [Profile::inline_files].
+    pub async fn import_file<'c, P: AsRef<Path>>(
+        &self,
+        path: P,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<Configuration<'c>> {
+        let path = path.as_ref();
+        let profile = Profile::from_file(path)?;
+        let name = path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy();
+
+        self.import_profile(&name, &profile, single_use, persistent)
+            .await
+    }
+
     /// Fetch all sessions that are available to the user.
     pub async fn sessions(&'a self) -> Result<Vec<Session<'a>>> {
-        self.ping().await?;
+        self.ping(Service::Sessions).await?;
 
         let sessions = self.sessions_proxy.fetch_available_sessions().await?;
 
         futures_util::future::join_all(sessions.into_iter().map(|object_path| {
-            Session::new(
+            Session::with_destinations(
                 self.connection.clone(),
+                &self.destinations,
                 /*&self.sessions_proxy,*/ object_path,
             )
         }))
@@ -106,14 +192,22 @@ impl<'a> OpenVPN3<'a> {
     }
 
     pub async fn interfaces(&'a self) -> Result<Vec<String>> {
-        self.ping().await?;
+        self.ping(Service::Sessions).await?;
 
         let interfaces = self.sessions_proxy.fetch_managed_interfaces().await?;
         Ok(interfaces)
     }
 
-    pub async fn net_cfg_manager(&'a self) -> Result<NetCfgProxy<'static>> {
-        Ok(NetCfgProxy::new(&self.connection).await?)
+    pub async fn net_cfg_manager(&self) -> Result<NetCfgProxy<'static>> {
+        Ok(NetCfgProxy::builder(&self.connection)
+            .destination(self.destinations.get(Service::NetCfg).to_owned())?
+            .build()
+            .await?)
+    }
+
+    /// Get a [LogService] to inspect and configure the OpenVPN 3 log service.
+    pub async fn log_service(&self) -> Result<LogService<'static>> {
+        LogService::new(&self.connection, self.destinations.get(Service::Log)).await
     }
 
     pub async fn event_stream(&self) -> Result<SessionManagerEventStream<'a>> {
@@ -124,19 +218,148 @@ impl<'a> OpenVPN3<'a> {
         Ok(self.sessions_proxy.receive_log().await?)
     }
 
-    async fn ping(&'a self) -> Result<()> {
-        let mut attempts = 10;
+    /// Get a merged stream of [LogEvent]s sent by the session manager, the configuration manager and the network configuration service.
+    ///
+    /// This includes the Log signals of every VPN session and virtual network interface, including ones created after the stream.
+    /// Log signals which cannot be parsed are skipped.
+    pub async fn all_logs(&self) -> Result<impl Stream<Item = LogEvent> + 'a> {
+        let mut streams = Vec::new();
 
-        while attempts > 0 {
-            let results = future::join(self.peer_proxy.ping(), self.sessions_proxy.version()).await;
+        for service in [Service::Sessions, Service::Configuration, Service::NetCfg] {
+            // no object path, to also match the signals of session and device objects
+            let rule = MatchRule::builder()
+                .msg_type(MessageType::Signal)
+                .sender(self.destination(service))?
+                .interface(service.interface())?
+                .member("Log")?
+                .build();
+            let messages = MessageStream::for_match_rule(rule, &self.connection, None).await?;
+
+            streams.push(messages.filter_map(move |message| {
+                future::ready(
+                    message
+                        .ok()
+                        .and_then(|message| LogEvent::from_message(service, message).ok()),
+                )
+            }));
+        }
 
-            if results.0.is_ok() && results.1.is_ok() {
+        Ok(stream::select_all(streams))
+    }
+
+    /// Wait until the OpenVPN 3 services respond.
+    ///
+    /// Each service is started through D-Bus activation if needed, and is then probed by calling `Ping` and reading its `version` property, backing off exponentially between attempts.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for all services together.
+    ///
+    /// # Returns
+    ///
+    /// [Error::ServiceUnavailable] for the first service which did not respond in time.
+    pub async fn wait_for_services(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+
+        for service in Service::all() {
+            self.wait_for_service(service, deadline).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_service(&self, service: Service, deadline: Instant) -> Result<()> {
+        let destination = self.destination(service);
+
+        if let Ok(name) = WellKnownName::try_from(destination) {
+            // Not every bus allows activation, the probes below tell whether the service is running.
+            if let Ok(dbus) = DBusProxy::new(&self.connection).await {
+                let _ = dbus.start_service_by_name(name, 0).await;
+            }
+        }
+
+        let peer = PeerProxy::builder(&self.connection)
+            .destination(destination.to_owned())?
+            .path(service.path())?
+            .build()
+            .await?;
+        let properties = self.properties_proxy(service).await?;
+
+        let mut delay = Self::BACKOFF_INITIAL;
+        loop {
+            let probe = async {
+                peer.ping().await?;
+                properties
+                    .get(
+                        InterfaceName::from_static_str_unchecked(service.interface()),
+                        "version",
+                    )
+                    .await?;
+                Ok::<(), Error>(())
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if let Ok(Ok(())) = runtime::timeout(remaining, probe).await {
                 return Ok(());
             }
 
-            attempts -= 1;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::ServiceUnavailable { service });
+            }
+
+            runtime::sleep(delay.min(remaining)).await;
+            delay = (delay * 2).min(Self::BACKOFF_MAX);
         }
+    }
 
-        Ok(())
+    /// Fetch the version of an OpenVPN 3 service.
+    pub async fn service_version(&self, service: Service) -> Result<ServiceVersion> {
+        ServiceVersion::fetch(&self.connection, self.destination(service), service).await
+    }
+
+    /// Fetch the versions of all OpenVPN 3 services.
+    pub async fn service_versions(&self) -> Result<ServiceVersions> {
+        ServiceVersions::fetch(&self.connection, &self.destinations).await
+    }
+
+    /// Check whether the services support Data Channel Offload.
+    ///
+    /// The answer is cached, and shared with the sessions and configuration profiles of this client.
+    pub async fn supports_dco(&self) -> Result<bool> {
+        match ServiceVersions::require_dco(&self.connection, &self.destinations).await {
+            Ok(()) => Ok(true),
+            Err(Error::Unsupported(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Check whether the log service can proxy Log events to other D-Bus services.
+    pub async fn supports_log_proxy(&self) -> Result<bool> {
+        Ok(self.service_versions().await?.supports_log_proxy())
+    }
+
+    /// Check whether the Data Channel Offload kernel module is available.
+    ///
+    /// # Returns
+    ///
+    /// [Error::Unsupported] if the services are too old to support Data Channel Offload.
+    pub async fn dco_available(&self) -> Result<bool> {
+        ServiceVersions::require_dco(&self.connection, &self.destinations).await?;
+
+        Ok(self.net_cfg_manager().await?.dco_available().await?)
+    }
+
+    async fn properties_proxy(&self, service: Service) -> Result<PropertiesProxy<'static>> {
+        Ok(PropertiesProxy::builder(&self.connection)
+            .destination(self.destination(service).to_owned())?
+            .path(service.path())?
+            .build()
+            .await?)
+    }
+
+    async fn ping(&self, service: Service) -> Result<()> {
+        self.wait_for_service(service, Instant::now() + Self::PING_TIMEOUT)
+            .await
     }
 }
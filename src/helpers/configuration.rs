@@ -1,8 +1,14 @@
 //! Provides an interface to communicate with the OpenVPN 3 configuration D-Bus API.
 
-use super::Session;
+use super::{
+    access::AccessEntry, builder::Destinations, ConfigAccess, ConfigOverride, OverrideKind,
+    Service, ServiceVersions, Session, StaticChallenge, User,
+};
 
-use crate::{ConfigurationNodeProxy, Result, SessionsProxy};
+use crate::{
+    profile::{validate, Profile, ValidationReport},
+    ConfigurationNodeProxy, ConfigurationProxy, Result, SessionsProxy,
+};
 
 use zbus::{
     zvariant::{OwnedObjectPath, OwnedValue},
@@ -16,24 +22,31 @@ pub struct Configuration<'a> {
     pub(crate) path: OwnedObjectPath,
     pub(crate) proxy: ConfigurationNodeProxy<'a>,
     pub(crate) sessions_proxy: SessionsProxy<'a>,
+    pub(crate) destinations: Destinations,
 }
 
 impl<'a> Configuration<'a> {
-    const DBUS_INTERFACE: &'static str = "net.openvpn.v3.configuration";
-
     /// Constructs a new [Configuration] that represents a single OpenVPN 3 VPN configuration profile through the D-Bus API.
     ///
     /// # Arguments
     ///
     /// * `connection` - D-Bus [Connection] instance.
     /// * `path` - D-Bus [OwnedObjectPath] to the configuration profile.
-    pub(crate) async fn new(
+    pub async fn new(connection: Connection, path: OwnedObjectPath) -> Result<Configuration<'a>> {
+        Self::with_destinations(connection, &Destinations::default(), path).await
+    }
+
+    pub(crate) async fn with_destinations(
         connection: Connection,
+        destinations: &Destinations,
         path: OwnedObjectPath,
     ) -> Result<Configuration<'a>> {
-        let sessions_proxy = SessionsProxy::new(&connection).await?;
+        let sessions_proxy = SessionsProxy::builder(&connection)
+            .destination(destinations.get(Service::Sessions).to_owned())?
+            .build()
+            .await?;
         let proxy = ConfigurationNodeProxy::builder(&connection)
-            .destination(Self::DBUS_INTERFACE)?
+            .destination(destinations.get(Service::Configuration).to_owned())?
             .path(path.clone())?
             .cache_properties(CacheProperties::No)
             .build()
@@ -44,17 +57,19 @@ impl<'a> Configuration<'a> {
             path,
             proxy,
             sessions_proxy,
+            destinations: destinations.clone(),
         })
     }
 
     /// Start a new VPN backend client process for this VPN configuration profile.
     pub async fn new_tunnel<'c>(&self) -> Result<Session<'c>> {
         let proxy = self.sessions_proxy.new_tunnel(&self.path).await?;
-        Ok(Session::new(
+        Session::with_destinations(
             self.connection.clone(),
+            &self.destinations,
             OwnedObjectPath::from(proxy.path().clone()),
         )
-        .await?)
+        .await
     }
 
     /// Fetch the configuration as a string blob.
@@ -69,6 +84,141 @@ impl<'a> Configuration<'a> {
         )?)
     }
 
+    /// Fetch the configuration and parse it as a [Profile].
+    pub async fn profile(&'a self) -> Result<Profile> {
+        Profile::parse(&self.fetch().await?)
+    }
+
+    /// Fetch the configuration and check it for problems, see [validate].
+    ///
+    /// This explains why the `valid` property is `false`, or why a session fails with [StatusMinor::CfgError](crate::sessions_node::constants::StatusMinor::CfgError), before a tunnel is started.
+    pub async fn validate(&'a self) -> Result<ValidationReport> {
+        Ok(validate(&self.fetch().await?))
+    }
+
+    /// Fetch the [StaticChallenge] defined by this configuration profile, if any.
+    ///
+    /// This allows the challenge to be presented to the user before a session is started.
+    pub async fn static_challenge(&'a self) -> Result<Option<StaticChallenge>> {
+        Ok(StaticChallenge::from_config(&self.fetch().await?))
+    }
+
+    /// Set an override, replacing its previous value.
+    pub async fn set_override(&'a self, setting: &ConfigOverride) -> Result<()> {
+        Ok(self
+            .proxy
+            .set_override(setting.kind().name(), &setting.value())
+            .await?)
+    }
+
+    /// Get all overrides which are set.
+    ///
+    /// Overrides which are not known to this crate, e.g. ones added by newer OpenVPN 3 services, are skipped,
+    /// as are overrides whose value cannot be decoded.
+    pub async fn overrides(&'a self) -> Result<Vec<ConfigOverride>> {
+        let overrides = self.proxy.overrides().await?;
+        let mut overrides: Vec<ConfigOverride> = overrides
+            .iter()
+            .filter_map(|(name, value)| {
+                let kind = OverrideKind::parse(name)?;
+                ConfigOverride::from_value(kind, value).ok()
+            })
+            .collect();
+
+        overrides.sort_by_key(|setting| setting.kind().name());
+        Ok(overrides)
+    }
+
+    /// Remove an override, restoring the setting of the profile.
+    pub async fn unset_override(&'a self, kind: OverrideKind) -> Result<()> {
+        Ok(self.proxy.unset_override(kind.name()).await?)
+    }
+
+    /// Check whether sessions started from this profile use Data Channel Offload.
+    ///
+    /// # Returns
+    ///
+    /// [crate::Error::Unsupported] if the services are too old to support Data Channel Offload.
+    pub async fn dco(&self) -> Result<bool> {
+        ServiceVersions::require_dco(&self.connection, &self.destinations).await?;
+        Ok(self.proxy.dco().await?)
+    }
+
+    /// Enable or disable Data Channel Offload for sessions started from this profile.
+    ///
+    /// # Returns
+    ///
+    /// [crate::Error::Unsupported] if the services are too old to support Data Channel Offload.
+    pub async fn set_dco(&self, enabled: bool) -> Result<()> {
+        ServiceVersions::require_dco(&self.connection, &self.destinations).await?;
+        Ok(self.proxy.set_dco(enabled).await?)
+    }
+
+    /// Get a snapshot of who can use this profile, and how it is protected.
+    ///
+    /// User names are looked up in the local user database.
+    pub async fn access(&'a self) -> Result<ConfigAccess> {
+        let mut uids = vec![self.proxy.owner().await?];
+        uids.extend(self.proxy.acl().await?);
+        let mut entries = AccessEntry::lookup(uids).await;
+        let owner = entries.remove(0);
+
+        Ok(ConfigAccess {
+            owner,
+            acl: entries,
+            public_access: self.proxy.public_access().await?,
+            locked_down: self.proxy.locked_down().await?,
+            sealed: self.proxy.readonly().await?,
+        })
+    }
+
+    /// Grant a user access to this profile.
+    pub async fn grant<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        Ok(self
+            .proxy
+            .access_grant(user.into().resolve().await?)
+            .await?)
+    }
+
+    /// Revoke the access of a user to this profile. The owner cannot have its access revoked.
+    pub async fn revoke<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        Ok(self
+            .proxy
+            .access_revoke(user.into().resolve().await?)
+            .await?)
+    }
+
+    /// Allow or disallow all users to use this profile.
+    pub async fn set_public(&'a self, public: bool) -> Result<()> {
+        Ok(self.proxy.set_public_access(public).await?)
+    }
+
+    /// Restrict users other than the owner to starting tunnels, so they cannot retrieve the profile.
+    pub async fn lock_down(&'a self, locked_down: bool) -> Result<()> {
+        Ok(self.proxy.set_locked_down(locked_down).await?)
+    }
+
+    /// Make this profile read-only, so it can no longer be modified or removed.
+    ///
+    /// This cannot be undone.
+    pub async fn seal(&'a self) -> Result<()> {
+        Ok(self.proxy.seal().await?)
+    }
+
+    /// Transfer the ownership of this profile to another user.
+    ///
+    /// The configuration manager only allows this for the root user.
+    pub async fn transfer_to<U: Into<User>>(&'a self, user: U) -> Result<()> {
+        let uid = user.into().resolve().await?;
+        let manager = ConfigurationProxy::builder(&self.connection)
+            .destination(self.destinations.get(Service::Configuration).to_owned())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        Ok(manager.transfer_ownership(&self.path, uid).await?)
+    }
+
     /// Removes this VPN configuration profile.
     pub async fn remove(&'a self) -> Result<()> {
         Ok(self.proxy.remove().await?)
@@ -0,0 +1,219 @@
+//! Providers which answer [UserInputSlot] requests automatically.
+
+use super::{DynamicChallenge, UserInputSlot};
+
+use crate::sessions_node::constants::ClientAttentionGroup;
+
+/// Credential Provider
+///
+/// Answers requests for user input by the backend VPN process. Each [ClientAttentionGroup] has its own callback, which returns `None` by default.
+///
+/// Closures taking a [UserInputSlot] and returning an `Option<String>` implement this trait as well.
+pub trait CredentialProvider {
+    /// Answer a [ClientAttentionGroup::UserPassword] request.
+    fn user_password(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        None
+    }
+
+    /// Answer a [ClientAttentionGroup::HttpProxyCreds] request.
+    fn http_proxy_creds(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        None
+    }
+
+    /// Answer a [ClientAttentionGroup::PkPassphrase] request.
+    fn pk_passphrase(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        None
+    }
+
+    /// Answer a [ClientAttentionGroup::ChallengeStatic] request.
+    fn challenge_static(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        None
+    }
+
+    /// Answer a [ClientAttentionGroup::ChallengeDynamic] request.
+    ///
+    /// Use [UserInputSlot::dynamic_challenge] to parse the challenge and build a correctly formatted response.
+    fn challenge_dynamic(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        None
+    }
+
+    /// Answer any request, dispatching to the callback of the slot's [ClientAttentionGroup].
+    fn provide(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        match slot.type_group().1 {
+            ClientAttentionGroup::UserPassword => self.user_password(slot),
+            ClientAttentionGroup::HttpProxyCreds => self.http_proxy_creds(slot),
+            ClientAttentionGroup::PkPassphrase => self.pk_passphrase(slot),
+            ClientAttentionGroup::ChallengeStatic => self.challenge_static(slot),
+            ClientAttentionGroup::ChallengeDynamic => self.challenge_dynamic(slot),
+            _ => None,
+        }
+    }
+}
+
+impl<F> CredentialProvider for F
+where
+    F: Fn(&UserInputSlot<'_>) -> Option<String>,
+{
+    fn provide(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        self(slot)
+    }
+}
+
+/// Static Credentials
+///
+/// Answers requests with fixed values, keyed by [ClientAttentionGroup] and variable name.
+#[derive(Clone, Debug, Default)]
+pub struct StaticCredentials {
+    values: Vec<(ClientAttentionGroup, String, String)>,
+}
+
+impl StaticCredentials {
+    /// Construct an empty set of [StaticCredentials].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value for a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - [ClientAttentionGroup] of the request.
+    /// * `variable_name` - Internal variable name of the request, see [UserInputSlot::variable_name].
+    /// * `value` - Value to provide.
+    pub fn set(mut self, group: ClientAttentionGroup, variable_name: &str, value: &str) -> Self {
+        self.values
+            .retain(|(g, name, _)| *g != group || name != variable_name);
+        self.values
+            .push((group, variable_name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the username used for [ClientAttentionGroup::UserPassword] requests.
+    pub fn username(self, value: &str) -> Self {
+        self.set(ClientAttentionGroup::UserPassword, "username", value)
+    }
+
+    /// Set the password used for [ClientAttentionGroup::UserPassword] requests.
+    pub fn password(self, value: &str) -> Self {
+        self.set(ClientAttentionGroup::UserPassword, "password", value)
+    }
+
+    fn get(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        let group = slot.type_group().1;
+
+        self.values
+            .iter()
+            .find(|(g, name, _)| *g == group && name == slot.variable_name())
+            .map(|(_, _, value)| value.clone())
+    }
+}
+
+impl CredentialProvider for StaticCredentials {
+    fn provide(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        self.get(slot)
+    }
+}
+
+/// Environment Credentials
+///
+/// Answers requests from environment variables named after the request's variable name, e.g. `OPENVPN3_USERNAME` and `OPENVPN3_PASSWORD`.
+#[derive(Clone, Debug)]
+pub struct EnvCredentials {
+    prefix: String,
+}
+
+impl EnvCredentials {
+    /// Construct [EnvCredentials] which use the `OPENVPN3_` prefix.
+    pub fn new() -> Self {
+        Self::with_prefix("OPENVPN3")
+    }
+
+    /// Construct [EnvCredentials] which use a custom prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Prefix of the environment variable names, without the trailing underscore.
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Name of the environment variable used to answer a request with the given variable name.
+    pub fn variable(&self, variable_name: &str) -> String {
+        let name: String = variable_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        format!("{}_{}", self.prefix, name)
+    }
+}
+
+impl Default for EnvCredentials {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn provide(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        std::env::var(self.variable(slot.variable_name())).ok()
+    }
+}
+
+/// Credential Set
+///
+/// Answers username/password requests, as well as static and dynamic challenges with a one-time password (OTP).
+#[derive(Clone, Debug, Default)]
+pub struct CredentialSet {
+    username: Option<String>,
+    password: Option<String>,
+    otp: Option<String>,
+}
+
+impl CredentialSet {
+    /// Construct a [CredentialSet] with a username and password.
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: Some(username.to_string()),
+            password: Some(password.to_string()),
+            otp: None,
+        }
+    }
+
+    /// Set the one-time password used to answer static and dynamic challenges.
+    pub fn otp(mut self, otp: &str) -> Self {
+        self.otp = Some(otp.to_string());
+        self
+    }
+}
+
+impl CredentialProvider for CredentialSet {
+    fn user_password(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        match slot.variable_name() {
+            "username" => self.username.clone(),
+            "password" => self.password.clone(),
+            _ => None,
+        }
+    }
+
+    fn challenge_static(&self, _slot: &UserInputSlot<'_>) -> Option<String> {
+        self.otp.clone()
+    }
+
+    fn challenge_dynamic(&self, slot: &UserInputSlot<'_>) -> Option<String> {
+        let otp = self.otp.as_deref()?;
+
+        match DynamicChallenge::from_slot(slot) {
+            Ok(Some(challenge)) => Some(challenge.response(otp)),
+            _ => None,
+        }
+    }
+}
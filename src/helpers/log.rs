@@ -0,0 +1,363 @@
+//! Provides an interface to communicate with the OpenVPN 3 log D-Bus API.
+
+use super::{Service, ServiceVersion, ServiceVersions};
+
+use crate::{
+    configuration,
+    log::constants::{LogCategory, LogGroup, LogLevel},
+    netcfg, netcfg_node, sessions, sessions_node, Error, LogNodeProxy, LogProxy, Result,
+};
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc, time::SystemTime};
+use zbus::{
+    zvariant::{ObjectPath, OwnedObjectPath},
+    CacheProperties, Connection, Message,
+};
+
+/// OpenVPN 3 Log Service
+///
+/// Receives Log events from all of the OpenVPN 3 Linux services, and controls how they are logged.
+#[derive(Clone, Debug)]
+pub struct LogService<'a> {
+    pub(crate) proxy: LogProxy<'a>,
+}
+
+impl<'a> LogService<'a> {
+    /// Constructs a new [LogService] which communicates with the OpenVPN 3 log service through the D-Bus API.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - D-Bus [Connection] instance.
+    /// * `destination` - Bus name of the log service.
+    pub(crate) async fn new(connection: &Connection, destination: &str) -> Result<LogService<'a>> {
+        let proxy = LogProxy::builder(connection)
+            .destination(destination.to_owned())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        Ok(Self { proxy })
+    }
+
+    /// Fetch all Log signal producers the log service is attached to.
+    pub async fn subscribers(&self) -> Result<Vec<LogSubscriber>> {
+        Ok(self
+            .proxy
+            .get_subscriber_list()
+            .await?
+            .into_iter()
+            .map(LogSubscriber::from)
+            .collect())
+    }
+
+    /// Attach the log service to a Log signal producer.
+    ///
+    /// # Arguments
+    ///
+    /// * `interface` - D-Bus interface of the Log signals to subscribe to.
+    pub async fn attach(&self, interface: &str) -> Result<()> {
+        Ok(self.proxy.attach(interface).await?)
+    }
+
+    /// Detach the log service from a Log signal producer.
+    ///
+    /// # Arguments
+    ///
+    /// * `interface` - D-Bus interface of the Log signals to unsubscribe from.
+    pub async fn detach(&self, interface: &str) -> Result<()> {
+        Ok(self.proxy.detach(interface).await?)
+    }
+
+    /// Get the log verbosity.
+    pub async fn log_level(&self) -> Result<LogLevel> {
+        Ok(self.proxy.log_level().await?)
+    }
+
+    /// Set the log verbosity.
+    pub async fn set_log_level(&self, value: LogLevel) -> Result<()> {
+        Ok(self.proxy.set_log_level(value).await?)
+    }
+
+    /// Get whether each log line is prefixed with a timestamp.
+    pub async fn timestamp(&self) -> Result<bool> {
+        Ok(self.proxy.timestamp().await?)
+    }
+
+    /// Set whether each log line is prefixed with a timestamp.
+    pub async fn set_timestamp(&self, value: bool) -> Result<()> {
+        Ok(self.proxy.set_timestamp(value).await?)
+    }
+
+    /// Get whether each Log event is preceded by details about its D-Bus sender.
+    pub async fn log_dbus_details(&self) -> Result<bool> {
+        Ok(self.proxy.log_dbus_details().await?)
+    }
+
+    /// Set whether each Log event is preceded by details about its D-Bus sender.
+    pub async fn set_log_dbus_details(&self, value: bool) -> Result<()> {
+        Ok(self.proxy.set_log_dbus_details(value).await?)
+    }
+
+    /// Get whether logged messages are prefixed with the sender's LogTag hash value.
+    pub async fn log_prefix_logtag(&self) -> Result<bool> {
+        Ok(self.proxy.log_prefix_logtag().await?)
+    }
+
+    /// Set whether logged messages are prefixed with the sender's LogTag hash value.
+    pub async fn set_log_prefix_logtag(&self, value: bool) -> Result<()> {
+        Ok(self.proxy.set_log_prefix_logtag(value).await?)
+    }
+
+    /// Get the logging method in use.
+    pub async fn log_method(&self) -> Result<String> {
+        Ok(self.proxy.log_method().await?)
+    }
+
+    /// Get the number of attached subscriptions.
+    pub async fn num_attached(&self) -> Result<u32> {
+        Ok(self.proxy.num_attached().await?)
+    }
+
+    /// Get version information about the running service.
+    pub async fn version(&self) -> Result<String> {
+        Ok(self.proxy.version().await?)
+    }
+
+    /// Forward the Log and StatusChange events of a session to another D-Bus service.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_address` - Unique bus name of the recipient.
+    /// * `session_path` - D-Bus object path of the VPN session.
+    ///
+    /// # Returns
+    ///
+    /// A proxy for the Log Proxy object, or [Error::Unsupported] if the log service is older than [ServiceVersions::LOG_PROXY].
+    pub async fn proxy_log_events(
+        &self,
+        target_address: &str,
+        session_path: &ObjectPath<'_>,
+    ) -> Result<LogNodeProxy<'a>> {
+        let version = ServiceVersion::parse(&self.version().await?);
+        if !version.at_least(ServiceVersions::LOG_PROXY) {
+            return Err(Error::Unsupported(format!(
+                "ProxyLogEvents (log service {})",
+                version
+            )));
+        }
+
+        Ok(self
+            .proxy
+            .proxy_log_events(target_address, session_path)
+            .await?)
+    }
+}
+
+/// Log Subscriber
+///
+/// A Log signal producer the log service is attached to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogSubscriber {
+    /// Tag value which is used in the logs.
+    pub tag: String,
+    /// Bus name the log service is attached to.
+    pub bus_name: String,
+    /// D-Bus object interface the subscription is tied to.
+    pub interface: String,
+    /// D-Bus object path the subscription is tied to.
+    pub path: String,
+}
+
+impl From<(String, String, String, String)> for LogSubscriber {
+    fn from(v: (String, String, String, String)) -> Self {
+        Self {
+            tag: v.0,
+            bus_name: v.1,
+            interface: v.2,
+            path: v.3,
+        }
+    }
+}
+
+/// Log Source
+///
+/// Identifies which object a [LogEvent] was sent by.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogSource {
+    /// A VPN session, identified by its D-Bus object path.
+    Session(OwnedObjectPath),
+    /// An OpenVPN 3 service, identified by its D-Bus service name.
+    Service(String),
+    /// A virtual network interface managed by the network configuration service, identified by its D-Bus object path.
+    NetCfgDevice(OwnedObjectPath),
+}
+
+impl fmt::Display for LogSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Session(path) => write!(f, "{}", path.as_str()),
+            Self::Service(name) => write!(f, "{}", name),
+            Self::NetCfgDevice(path) => write!(f, "{}", path.as_str()),
+        }
+    }
+}
+
+/// Log Event
+///
+/// A single Log signal, regardless of which service or object sent it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEvent {
+    /// Object which sent the Log signal.
+    pub source: LogSource,
+    /// Log group of the sender.
+    pub group: LogGroup,
+    /// Severity of the message.
+    pub severity: LogCategory,
+    /// Log message.
+    pub message: String,
+    /// Time the Log signal was received.
+    pub received: SystemTime,
+}
+
+impl LogEvent {
+    /// Construct a [LogEvent] received now.
+    pub fn new(source: LogSource, group: LogGroup, severity: LogCategory, message: &str) -> Self {
+        Self {
+            source,
+            group,
+            severity,
+            message: message.to_string(),
+            received: SystemTime::now(),
+        }
+    }
+
+    /// Construct a [LogEvent] from the `last_log` property of a VPN session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_path` - D-Bus object path of the VPN session.
+    /// * `log` - Value of the `last_log` property.
+    pub fn from_last_log(session_path: OwnedObjectPath, log: sessions_node::result::Log) -> Self {
+        Self::new(
+            LogSource::Session(session_path),
+            log.group,
+            log.category,
+            &log.message,
+        )
+    }
+
+    /// Construct a [LogEvent] from a Log signal sent by a service, either by its main object or by one of its session or device objects.
+    pub(crate) fn from_message(service: Service, message: Arc<Message>) -> Result<Self> {
+        let main = message
+            .path()
+            .is_some_and(|path| path.as_str() == service.path());
+        let mismatch = || Error::Zbus(zbus::Error::InvalidField);
+
+        match (service, main) {
+            (Service::Sessions, true) => sessions::Log::from_message(message)
+                .ok_or_else(mismatch)?
+                .try_into(),
+            (Service::Sessions, false) => sessions_node::Log::from_message(message)
+                .ok_or_else(mismatch)?
+                .try_into(),
+            (Service::Configuration, _) => configuration::Log::from_message(message)
+                .ok_or_else(mismatch)?
+                .try_into(),
+            (Service::NetCfg, true) => netcfg::Log::from_message(message)
+                .ok_or_else(mismatch)?
+                .try_into(),
+            (Service::NetCfg, false) => netcfg_node::Log::from_message(message)
+                .ok_or_else(mismatch)?
+                .try_into(),
+            (Service::Log, _) => Err(mismatch()),
+        }
+    }
+
+    fn object_path(message: &Message) -> Result<OwnedObjectPath> {
+        message
+            .path()
+            .map(OwnedObjectPath::from)
+            .ok_or(Error::Zbus(zbus::Error::MissingField))
+    }
+}
+
+impl fmt::Display for LogEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} {}: {}",
+            self.source, self.group, self.severity, self.message
+        )
+    }
+}
+
+impl TryFrom<sessions::Log> for LogEvent {
+    type Error = Error;
+
+    fn try_from(signal: sessions::Log) -> Result<Self> {
+        let args = signal.args()?;
+        Ok(Self::new(
+            LogSource::Service(String::from("net.openvpn.v3.sessions")),
+            args.group,
+            args.category,
+            args.message,
+        ))
+    }
+}
+
+impl TryFrom<sessions_node::Log> for LogEvent {
+    type Error = Error;
+
+    fn try_from(signal: sessions_node::Log) -> Result<Self> {
+        let args = signal.args()?;
+        Ok(Self::new(
+            LogSource::Session(Self::object_path(&signal)?),
+            args.group,
+            args.category,
+            args.message,
+        ))
+    }
+}
+
+impl TryFrom<configuration::Log> for LogEvent {
+    type Error = Error;
+
+    fn try_from(signal: configuration::Log) -> Result<Self> {
+        let args = signal.args()?;
+        Ok(Self::new(
+            LogSource::Service(String::from("net.openvpn.v3.configuration")),
+            args.group,
+            args.category,
+            args.message,
+        ))
+    }
+}
+
+impl TryFrom<netcfg::Log> for LogEvent {
+    type Error = Error;
+
+    fn try_from(signal: netcfg::Log) -> Result<Self> {
+        let args = signal.args()?;
+        Ok(Self::new(
+            LogSource::Service(String::from("net.openvpn.v3.netcfg")),
+            args.group,
+            args.category,
+            args.message,
+        ))
+    }
+}
+
+impl TryFrom<netcfg_node::Log> for LogEvent {
+    type Error = Error;
+
+    fn try_from(signal: netcfg_node::Log) -> Result<Self> {
+        let args = signal.args()?;
+        Ok(Self::new(
+            LogSource::NetCfgDevice(Self::object_path(&signal)?),
+            args.group,
+            args.category,
+            args.message,
+        ))
+    }
+}
@@ -2,9 +2,10 @@
 //!
 //! # Examples
 //!
-//! ```
+//! ```no_run
 //! use async_std::task;
-//! use openvpn3_rs::helpers::OpenVPN3;
+//! use openvpn3_rs::helpers::{OpenVPN3, StaticCredentials};
+//! use std::time::Duration;
 //!
 //! fn main() {
 //!     task::block_on(async {
@@ -17,37 +18,56 @@
 //!         let config = openvpn3.import("VPN", config_str, true, true).await.unwrap();
 //!         let session = config.new_tunnel().await.unwrap();
 //!     
-//!         let mut ready = false;
-//!         while !ready {
-//!             if let Err(err) = session.ready().await {
-//!                 if err == openvpn3_rs::Error::MissingUserCredentials {
-//!                     for ui in session.fetch_user_input_slots().await.unwrap() {
-//!                         let var_name = ui.variable_name();
-//!                         if var_name == "username" {
-//!                             ui.provide_input("smith").await.unwrap();
-//!                         }
-//!                         if var_name == "password" {
-//!                             ui.provide_input("hunter2").await.unwrap();
-//!                         }
-//!                     }
-//!                 } else if err == openvpn3_rs::Error::BackendNotReady {
-//!                     task::sleep(std::time::Duration::from_secs(1));
-//!                 }
-//!             } else {
-//!                 ready = true;
-//!             }
-//!         }
-//!         
-//!         session.connect().await.unwrap();
+//!         let credentials = StaticCredentials::new()
+//!             .username("smith")
+//!             .password("hunter2");
+//!
+//!         session
+//!             .connect_with(&credentials, Duration::from_secs(30))
+//!             .await
+//!             .unwrap();
 //!
 //!     });
 //! }
 //! ```
+//!
+//! # Runtimes
+//!
+//! Timers and the D-Bus connection run on async-io by default. Enable the `tokio` feature, and disable default features, to run everything on the ambient tokio runtime instead.
 
+mod access;
+pub mod blocking;
+#[cfg(any(feature = "tracing", feature = "log"))]
+mod bridge;
+mod builder;
+mod challenge;
 mod client;
 mod configuration;
+mod credentials;
+mod log;
+mod overrides;
+mod recorder;
 mod session;
+mod state;
+mod statistics;
+mod version;
+mod web_auth;
 
+pub use access::{AccessEntry, ConfigAccess, User};
+#[cfg(feature = "log")]
+pub use bridge::forward_to_log;
+#[cfg(feature = "tracing")]
+pub use bridge::forward_to_tracing;
+pub use builder::{OpenVPN3Builder, Service};
+pub use challenge::{DynamicChallenge, StaticChallenge};
 pub use client::OpenVPN3;
 pub use configuration::Configuration;
+pub use credentials::{CredentialProvider, CredentialSet, EnvCredentials, StaticCredentials};
+pub use log::{LogEvent, LogService, LogSource, LogSubscriber};
+pub use overrides::{Compression, ConfigOverride, DnsScope, Ipv6Mode, OverrideKind};
+pub use recorder::{read_log_file, LogFilter, LogReader, LogRecord, LogRecorder};
 pub use session::{Session, UserInputSlot};
+pub use state::SessionState;
+pub use statistics::{SessionStatistics, StatisticsRates, StatisticsSample, StatisticsSampler};
+pub use version::{ServiceVersion, ServiceVersions};
+pub use web_auth::{PrintUrl, WebAuthOpener, WebAuthRequest, XdgOpen};
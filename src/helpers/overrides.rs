@@ -0,0 +1,256 @@
+//! Typed override settings of configuration profiles.
+
+use crate::{profile::Proto, Error, Result};
+
+use std::fmt;
+use zbus::zvariant::{OwnedValue, Value};
+
+/// IPv6 mode of the `ipv6` override.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ipv6Mode {
+    /// Allow IPv6 traffic through the tunnel.
+    Yes,
+    /// Block IPv6 traffic through the tunnel.
+    No,
+    /// Use IPv6 if the server pushes an IPv6 address.
+    Default,
+}
+
+/// Compression mode of the `allow-compression` override.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Refuse compression.
+    No,
+    /// Decompress packets from the server, but do not compress packets sent to it.
+    Asym,
+    /// Compress in both directions.
+    Yes,
+}
+
+/// DNS scope of the `dns-scope` override.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DnsScope {
+    /// Resolve all DNS queries through the VPN.
+    Global,
+    /// Resolve only the domains pushed by the server through the VPN.
+    Tunnel,
+}
+
+macro_rules! keyword_enum {
+    ($name:ident { $($variant:ident => $keyword:literal),+ $(,)? }) => {
+        impl $name {
+            /// Parse the value as used by the configuration manager.
+            pub fn parse(value: &str) -> Option<Self> {
+                match value {
+                    $($keyword => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$variant => write!(f, $keyword),)+
+                }
+            }
+        }
+    };
+}
+
+keyword_enum!(Ipv6Mode {
+    Yes => "yes",
+    No => "no",
+    Default => "default",
+});
+keyword_enum!(Compression {
+    No => "no",
+    Asym => "asym",
+    Yes => "yes",
+});
+keyword_enum!(DnsScope {
+    Global => "global",
+    Tunnel => "tunnel",
+});
+
+/// Conversion of override values to and from their D-Bus representation.
+trait OverrideValue: Sized {
+    fn to_value(&self) -> Value<'static>;
+    fn from_value(value: &OwnedValue) -> Option<Self>;
+}
+
+impl OverrideValue for bool {
+    fn to_value(&self) -> Value<'static> {
+        Value::from(*self)
+    }
+
+    fn from_value(value: &OwnedValue) -> Option<Self> {
+        bool::try_from(value.clone()).ok()
+    }
+}
+
+macro_rules! string_value {
+    ($($ty:ty => $parse:expr),+ $(,)?) => {
+        $(impl OverrideValue for $ty {
+            fn to_value(&self) -> Value<'static> {
+                Value::from(self.to_string())
+            }
+
+            fn from_value(value: &OwnedValue) -> Option<Self> {
+                String::try_from(value.clone()).ok().and_then(|value| $parse(&value))
+            }
+        })+
+    };
+}
+
+string_value! {
+    String => |value: &str| Some(value.to_string()),
+    u16 => |value: &str| value.parse().ok(),
+    Proto => Proto::parse,
+    Ipv6Mode => Ipv6Mode::parse,
+    Compression => Compression::parse,
+    DnsScope => DnsScope::parse,
+}
+
+macro_rules! overrides {
+    ($(
+        $(#[$doc:meta])*
+        $variant:ident($ty:ty) => $name:literal
+    ),+ $(,)?) => {
+        /// Configuration Override
+        ///
+        /// A setting applied by the configuration manager on top of a configuration profile, see [Configuration::set_override](super::Configuration::set_override).
+        ///
+        /// Booleans are sent as such, all other values as strings.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use openvpn3_rs::helpers::{ConfigOverride, OverrideKind};
+        ///
+        /// let port = ConfigOverride::PortOverride(443);
+        ///
+        /// assert_eq!(port.kind(), OverrideKind::PortOverride);
+        /// assert_eq!(port.to_string(), "port-override=443");
+        /// assert_eq!(OverrideKind::parse("dns-sync-lookup"), Some(OverrideKind::DnsSyncLookup));
+        /// ```
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum ConfigOverride {
+            $($(#[$doc])* $variant($ty),)+
+        }
+
+        /// Configuration Override Kind
+        ///
+        /// The name of a [ConfigOverride], without its value.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum OverrideKind {
+            $($(#[$doc])* $variant,)+
+        }
+
+        impl OverrideKind {
+            /// All overrides known to this crate.
+            pub const ALL: &'static [OverrideKind] = &[$(Self::$variant,)+];
+
+            /// Get the name of the override in the D-Bus API, e.g. `server-override`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)+
+                }
+            }
+
+            /// Parse the name of an override in the D-Bus API.
+            pub fn parse(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl ConfigOverride {
+            /// Get the [OverrideKind] of this override.
+            pub fn kind(&self) -> OverrideKind {
+                match self {
+                    $(Self::$variant(_) => OverrideKind::$variant,)+
+                }
+            }
+
+            /// Get the value as sent to the configuration manager.
+            pub fn value(&self) -> Value<'static> {
+                match self {
+                    $(Self::$variant(value) => value.to_value(),)+
+                }
+            }
+
+            /// Parse a value reported by the `overrides` property of a configuration profile.
+            ///
+            /// # Returns
+            ///
+            /// [Error::InvalidInput] if the value does not have the type, or is not one of the values, expected for `kind`.
+            pub fn from_value(kind: OverrideKind, value: &OwnedValue) -> Result<Self> {
+                let parsed = match kind {
+                    $(OverrideKind::$variant => OverrideValue::from_value(value).map(Self::$variant),)+
+                };
+
+                parsed.ok_or_else(|| Error::InvalidInput(format!("invalid value for override {}", kind)))
+            }
+        }
+    };
+}
+
+overrides! {
+    /// Connect to this server instead of the remotes of the profile.
+    ServerOverride(String) => "server-override",
+    /// Connect to this port instead of the one of the profile.
+    PortOverride(u16) => "port-override",
+    /// Connect using this protocol instead of the one of the profile.
+    ProtoOverride(Proto) => "proto-override",
+    /// Whether to route IPv6 traffic through the tunnel.
+    Ipv6(Ipv6Mode) => "ipv6",
+    /// Keep the virtual network interface while reconnecting.
+    PersistTun(bool) => "persist-tun",
+    /// Use Google's DNS servers if the server pushes none.
+    DnsFallbackGoogle(bool) => "dns-fallback-google",
+    /// Do not configure DNS servers pushed by the server.
+    DnsSetupDisabled(bool) => "dns-setup-disabled",
+    /// Which DNS queries are resolved through the VPN.
+    DnsScope(DnsScope) => "dns-scope",
+    /// Resolve host names synchronously.
+    DnsSyncLookup(bool) => "dns-sync-lookup",
+    /// Retry after an authentication failure instead of disconnecting.
+    AuthFailRetry(bool) => "auth-fail-retry",
+    /// Whether compression may be used.
+    AllowCompression(Compression) => "allow-compression",
+    /// Allow ciphers and digests which are disabled by default, e.g. `BF-CBC`.
+    EnableLegacyAlgorithms(bool) => "enable-legacy-algorithms",
+    /// Minimum TLS version, e.g. `1.2`.
+    TlsVersionMin(String) => "tls-version-min",
+    /// TLS certificate profile, e.g. `preferred`.
+    TlsCertProfile(String) => "tls-cert-profile",
+    /// Connect through this HTTP proxy.
+    ProxyHost(String) => "proxy-host",
+    /// Port of the HTTP proxy.
+    ProxyPort(u16) => "proxy-port",
+    /// User name to authenticate to the HTTP proxy with.
+    ProxyUsername(String) => "proxy-username",
+    /// Password to authenticate to the HTTP proxy with.
+    ProxyPassword(String) => "proxy-password",
+    /// Allow sending the proxy credentials in clear text.
+    ProxyAuthCleartext(bool) => "proxy-auth-cleartext",
+}
+
+impl fmt::Display for OverrideKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Display for ConfigOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value() {
+            Value::Str(value) => write!(f, "{}={}", self.kind(), value),
+            Value::Bool(value) => write!(f, "{}={}", self.kind(), value),
+            _ => unreachable!("override values are strings or booleans"),
+        }
+    }
+}
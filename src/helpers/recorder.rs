@@ -0,0 +1,587 @@
+//! Persistent capture of Log signals to rotating JSON Lines files.
+
+use super::{LogEvent, LogSource, OpenVPN3, Session};
+
+use crate::{
+    log::constants::{LogCategory, LogGroup},
+    Result,
+};
+
+use futures_util::{
+    future,
+    stream::{self, PollNext},
+    Stream, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Log Record
+///
+/// A [LogEvent] as it is stored by the [LogRecorder], one JSON object per line.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// The recorded event.
+    #[serde(flatten)]
+    pub event: LogEvent,
+    /// Name of the configuration profile the event relates to, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_name: Option<String>,
+}
+
+/// Log Recorder
+///
+/// Writes [LogRecord]s to JSON Lines files in a directory, starting a new file when the current one grows too large or too old.
+///
+/// Recording only needs a shared reference, so one recorder can capture several streams at the same time.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::{
+///     helpers::{LogEvent, LogFilter, LogReader, LogRecorder, LogSource},
+///     log::constants::{LogCategory, LogGroup},
+/// };
+///
+/// let directory = std::env::temp_dir().join("openvpn3-rs-recorder-example");
+/// # let _ = std::fs::remove_dir_all(&directory);
+/// let recorder = LogRecorder::new(&directory).unwrap().max_size(1024 * 1024);
+///
+/// let event = LogEvent::new(
+///     LogSource::Service(String::from("net.openvpn.v3.sessions")),
+///     LogGroup::SESSIONMGR,
+///     LogCategory::INFO,
+///     "Session created",
+/// );
+/// recorder.record(event.clone(), Some("My VPN")).unwrap();
+///
+/// let records = LogReader::open(&directory)
+///     .unwrap()
+///     .read(&LogFilter::new().min_severity(LogCategory::INFO))
+///     .unwrap();
+///
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].event, event);
+/// assert_eq!(records[0].config_name.as_deref(), Some("My VPN"));
+/// # std::fs::remove_dir_all(&directory).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct LogRecorder {
+    directory: PathBuf,
+    prefix: String,
+    max_size: u64,
+    max_age: Duration,
+    max_files: Option<usize>,
+    state: Mutex<RecorderState>,
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    current: Option<RecorderFile>,
+    /// Timestamp in the name of the newest file, so file names never repeat.
+    last_timestamp: u128,
+}
+
+#[derive(Debug)]
+struct RecorderFile {
+    file: File,
+    size: u64,
+    opened: SystemTime,
+}
+
+impl LogRecorder {
+    const EXTENSION: &'static str = "jsonl";
+
+    /// Construct a [LogRecorder] which writes to files in `directory`, creating it if needed.
+    ///
+    /// By default, files are rotated when they reach 10 MiB or are a day old, and old files are kept.
+    pub fn new<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        fs::create_dir_all(directory.as_ref())?;
+
+        Ok(Self {
+            directory: directory.as_ref().to_path_buf(),
+            prefix: String::from("openvpn3"),
+            max_size: 10 * 1024 * 1024,
+            max_age: Duration::from_secs(24 * 60 * 60),
+            max_files: None,
+            state: Mutex::default(),
+        })
+    }
+
+    /// Set the prefix of the file names.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Set the size in bytes after which a new file is started.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Set the age after which a new file is started.
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.max_age = age;
+        self
+    }
+
+    /// Set the number of files to keep, the oldest files are removed when a new file is started.
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count);
+        self
+    }
+
+    /// Write a [LogRecord].
+    pub fn write(&self, record: &LogRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut state = self.state.lock().unwrap();
+        let rotate = match &state.current {
+            Some(current) => {
+                (current.size > 0 && current.size + line.len() as u64 > self.max_size)
+                    || current.opened.elapsed().unwrap_or_default() >= self.max_age
+            }
+            None => true,
+        };
+
+        if rotate {
+            self.rotate(&mut state)?;
+        }
+
+        if let Some(current) = &mut state.current {
+            current.file.write_all(&line)?;
+            current.size += line.len() as u64;
+        }
+
+        Ok(())
+    }
+
+    /// Record a [LogEvent].
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event to record.
+    /// * `config_name` - Name of the configuration profile the event relates to, if known.
+    pub fn record(&self, event: LogEvent, config_name: Option<&str>) -> Result<()> {
+        self.write(&LogRecord {
+            event,
+            config_name: config_name.map(String::from),
+        })
+    }
+
+    /// Record every [LogEvent] of a stream, until the stream ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - Stream of [LogEvent]s, e.g. from [OpenVPN3::all_logs] or [Session::log_events].
+    /// * `config_name` - Name of the configuration profile the events relate to, if known.
+    pub async fn record_stream<S>(&self, stream: S, config_name: Option<&str>) -> Result<()>
+    where
+        S: Stream<Item = LogEvent>,
+    {
+        futures_util::pin_mut!(stream);
+
+        while let Some(event) = stream.next().await {
+            self.record(event, config_name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record the Log signals of a VPN session, until the session is removed.
+    ///
+    /// The recording stops once the session reports a `SessRemoved` status change, or the session manager announces the session was destroyed.
+    pub async fn record_session(&self, session: &Session<'_>) -> Result<()> {
+        let config_name = session.config_name().await?;
+        let events = session.log_events().await?.map(Some);
+        let removed = session.removed_stream().await?.map(|()| None);
+
+        // prefer pending events, so those sent before the removal are still recorded
+        let events = stream::select_with_strategy(events, removed, |_: &mut ()| PollNext::Left)
+            .take_while(|event| future::ready(event.is_some()))
+            .filter_map(future::ready);
+
+        self.record_stream(events, Some(&config_name)).await
+    }
+
+    /// Record the Log signals of the session manager, the configuration manager and the network configuration service.
+    ///
+    /// # Examples
+    ///
+    /// Record a session and the services into the same files:
+    ///
+    /// ```no_run
+    /// # async_std::task::block_on(async {
+    /// use openvpn3_rs::helpers::{LogRecorder, OpenVPN3};
+    ///
+    /// let openvpn3 = OpenVPN3::connect().await.unwrap();
+    /// let session = openvpn3.sessions().await.unwrap().remove(0);
+    /// let recorder = LogRecorder::new("/var/log/openvpn3-rs").unwrap();
+    ///
+    /// let (session_result, manager_result) = futures_util::join!(
+    ///     recorder.record_session(&session),
+    ///     recorder.record_manager(&openvpn3),
+    /// );
+    /// # });
+    /// ```
+    pub async fn record_manager(&self, openvpn3: &OpenVPN3<'_>) -> Result<()> {
+        self.record_stream(openvpn3.all_logs().await?, None).await
+    }
+
+    fn rotate(&self, state: &mut RecorderState) -> Result<()> {
+        let now = SystemTime::now();
+        let timestamp = now
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros()
+            .max(state.last_timestamp + 1);
+        state.last_timestamp = timestamp;
+
+        let path = self.directory.join(format!(
+            "{}-{:020}.{}",
+            self.prefix,
+            timestamp,
+            Self::EXTENSION
+        ));
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        state.current = Some(RecorderFile {
+            file,
+            size,
+            opened: now,
+        });
+
+        if let Some(max_files) = self.max_files {
+            let files = log_files(&self.directory, &self.prefix)?;
+            let excess = files.len().saturating_sub(max_files.max(1));
+            for old in &files[..excess] {
+                fs::remove_file(old)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Is `name` the name of a file written by a [LogRecorder] with the given prefix, i.e. `<prefix>-<timestamp>.jsonl`?
+fn is_log_file(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .and_then(|rest| rest.strip_suffix(LogRecorder::EXTENSION))
+        .and_then(|rest| rest.strip_suffix('.'))
+        .is_some_and(|timestamp| {
+            timestamp.len() == 20 && timestamp.bytes().all(|b| b.is_ascii_digit())
+        })
+}
+
+/// List the files written by a [LogRecorder] with the given prefix, oldest first.
+fn log_files(directory: &Path, prefix: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| is_log_file(name, prefix));
+
+        if matches {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Read all [LogRecord]s from a single file written by a [LogRecorder].
+pub fn read_log_file<P: AsRef<Path>>(path: P) -> Result<Vec<LogRecord>> {
+    let mut records = Vec::new();
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+
+    Ok(records)
+}
+
+/// Log Filter
+///
+/// Selects which [LogRecord]s are returned by a [LogReader]. An empty filter matches every record.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    source: Option<LogSource>,
+    group: Option<LogGroup>,
+    min_severity: Option<LogCategory>,
+    config_name: Option<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+}
+
+impl LogFilter {
+    /// Construct a [LogFilter] which matches every record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match records sent by `source`.
+    pub fn source(mut self, source: LogSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Only match records of a log group.
+    pub fn group(mut self, group: LogGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Only match records at least as severe as `severity`.
+    pub fn min_severity(mut self, severity: LogCategory) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Only match records of a configuration profile.
+    pub fn config_name(mut self, config_name: &str) -> Self {
+        self.config_name = Some(config_name.to_string());
+        self
+    }
+
+    /// Only match records received at or after `time`.
+    pub fn since(mut self, time: SystemTime) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only match records received before `time`.
+    pub fn until(mut self, time: SystemTime) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Does the record match this filter?
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let event = &record.event;
+
+        self.source.as_ref().is_none_or(|s| *s == event.source)
+            && self.group.is_none_or(|g| g == event.group)
+            && self
+                .min_severity
+                .is_none_or(|s| event.severity as u32 >= s as u32)
+            && self
+                .config_name
+                .as_ref()
+                .is_none_or(|c| record.config_name.as_ref() == Some(c))
+            && self.since.is_none_or(|t| event.received >= t)
+            && self.until.is_none_or(|t| event.received < t)
+    }
+}
+
+/// Log Reader
+///
+/// Loads the [LogRecord]s written by a [LogRecorder] back from disk.
+#[derive(Clone, Debug)]
+pub struct LogReader {
+    files: Vec<PathBuf>,
+}
+
+impl LogReader {
+    /// Open the files written by a [LogRecorder] with the default prefix.
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        Self::open_with_prefix(directory, "openvpn3")
+    }
+
+    /// Open the files written by a [LogRecorder] with a custom prefix.
+    pub fn open_with_prefix<P: AsRef<Path>>(directory: P, prefix: &str) -> Result<Self> {
+        Ok(Self {
+            files: log_files(directory.as_ref(), prefix)?,
+        })
+    }
+
+    /// Files which will be read, oldest first.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Read all records matching the filter, oldest first.
+    pub fn read(&self, filter: &LogFilter) -> Result<Vec<LogRecord>> {
+        let mut records = Vec::new();
+
+        for file in &self.files {
+            records.extend(
+                read_log_file(file)?
+                    .into_iter()
+                    .filter(|record| filter.matches(record)),
+            );
+        }
+
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create an empty directory for a test.
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "openvpn3-rs-recorder-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn event(severity: LogCategory, message: &str) -> LogEvent {
+        LogEvent::new(
+            LogSource::Service(String::from("net.openvpn.v3.sessions")),
+            LogGroup::SESSIONMGR,
+            severity,
+            message,
+        )
+    }
+
+    #[test]
+    fn log_file_names() {
+        assert!(is_log_file(
+            "openvpn3-00000001700000000000.jsonl",
+            "openvpn3"
+        ));
+        assert!(!is_log_file(
+            "openvpn3-session-00000001700000000000.jsonl",
+            "openvpn3"
+        ));
+        assert!(!is_log_file("openvpn3-1700000000000.jsonl", "openvpn3"));
+        assert!(!is_log_file(
+            "openvpn3-00000001700000000000.json",
+            "openvpn3"
+        ));
+        assert!(!is_log_file(
+            "openvpn3-00000001700000000000.jsonl.bak",
+            "openvpn3"
+        ));
+        assert!(!is_log_file(
+            "openvpn3-0000000170000000000x.jsonl",
+            "openvpn3"
+        ));
+    }
+
+    #[test]
+    fn rotate_by_size() {
+        let directory = directory("rotate");
+        let recorder = LogRecorder::new(&directory).unwrap().max_size(1);
+
+        for message in ["one", "two", "three"] {
+            recorder
+                .record(event(LogCategory::INFO, message), None)
+                .unwrap();
+        }
+
+        let reader = LogReader::open(&directory).unwrap();
+        assert_eq!(reader.files().len(), 3);
+        for (file, message) in reader.files().iter().zip(["one", "two", "three"]) {
+            let records = read_log_file(file).unwrap();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].event.message, message);
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn prune_old_files() {
+        let directory = directory("prune");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("openvpn3-session.jsonl"), "").unwrap();
+
+        let recorder = LogRecorder::new(&directory)
+            .unwrap()
+            .max_size(1)
+            .max_files(2);
+
+        for message in ["one", "two", "three", "four"] {
+            recorder
+                .record(event(LogCategory::INFO, message), None)
+                .unwrap();
+        }
+
+        let records = LogReader::open(&directory)
+            .unwrap()
+            .read(&LogFilter::new())
+            .unwrap();
+        let messages: Vec<_> = records.iter().map(|r| r.event.message.as_str()).collect();
+        assert_eq!(messages, ["three", "four"]);
+
+        // files not written by the recorder are left alone
+        assert!(directory.join("openvpn3-session.jsonl").exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn filter() {
+        let session = LogSource::Session(
+            zbus::zvariant::OwnedObjectPath::try_from("/net/openvpn/v3/sessions/1").unwrap(),
+        );
+        let mut event = LogEvent::new(
+            session.clone(),
+            LogGroup::CLIENT,
+            LogCategory::WARN,
+            "warning",
+        );
+        event.received = UNIX_EPOCH + Duration::from_secs(100);
+        let record = LogRecord {
+            event,
+            config_name: Some(String::from("My VPN")),
+        };
+
+        assert!(LogFilter::new().matches(&record));
+
+        assert!(LogFilter::new().source(session).matches(&record));
+        assert!(!LogFilter::new()
+            .source(LogSource::Service(String::from("net.openvpn.v3.sessions")))
+            .matches(&record));
+
+        assert!(LogFilter::new().group(LogGroup::CLIENT).matches(&record));
+        assert!(!LogFilter::new()
+            .group(LogGroup::BACKENDPROC)
+            .matches(&record));
+
+        assert!(LogFilter::new()
+            .min_severity(LogCategory::INFO)
+            .matches(&record));
+        assert!(LogFilter::new()
+            .min_severity(LogCategory::WARN)
+            .matches(&record));
+        assert!(!LogFilter::new()
+            .min_severity(LogCategory::ERROR)
+            .matches(&record));
+
+        assert!(LogFilter::new().config_name("My VPN").matches(&record));
+        assert!(!LogFilter::new().config_name("Other").matches(&record));
+
+        let at = UNIX_EPOCH + Duration::from_secs(100);
+        assert!(LogFilter::new().since(at).matches(&record));
+        assert!(!LogFilter::new().until(at).matches(&record));
+        assert!(LogFilter::new()
+            .until(at + Duration::from_secs(1))
+            .matches(&record));
+        assert!(!LogFilter::new()
+            .since(at + Duration::from_secs(1))
+            .matches(&record));
+    }
+}
@@ -1,14 +1,23 @@
 //! Provides an interface to communicate with the OpenVPN 3 sessions D-Bus API.
 
+use super::{
+    builder::Destinations, CredentialProvider, DynamicChallenge, LogEvent, Service,
+    ServiceVersions, SessionState, StaticChallenge, WebAuthOpener, WebAuthRequest,
+};
+
 use crate::{
     proxy::sessions_node::{AttentionRequiredStream, LogStream, StatusChangeStream},
+    runtime,
+    sessions::constants::EventType,
     sessions_node::{
-        constants::{ClientAttentionGroup, ClientAttentionType},
+        constants::{ClientAttentionGroup, ClientAttentionType, StatusMinor},
         result::{Statistics, Status, UserInputQueueTypeGroup},
     },
-    Error, Result, SessionsNodeProxy,
+    Error, Result, SessionsNodeProxy, SessionsProxy,
 };
 
+use futures_util::{future, stream, Stream, StreamExt};
+use std::time::Duration;
 use zbus::{
     zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
     CacheProperties, Connection,
@@ -18,43 +27,97 @@ use zbus::{
 #[derive(Clone, Debug)]
 pub struct Session<'a> {
     pub(crate) proxy: SessionsNodeProxy<'a>,
+    pub(crate) destinations: Destinations,
 }
 
 impl<'a> Session<'a> {
-    const DBUS_INTERFACE: &'static str = "net.openvpn.v3.sessions";
+    const READY_RETRY_INTERVAL: Duration = Duration::from_millis(500);
 
     /// Constructs a new [Session] that represents a single OpenVPN 3 VPN session through the D-Bus API.
-    pub(crate) async fn new(
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - D-Bus [Connection] instance.
+    /// * `session_path` - D-Bus [OwnedObjectPath] to the VPN session.
+    pub async fn new(conn: Connection, session_path: OwnedObjectPath) -> Result<Session<'a>> {
+        Self::with_destinations(conn, &Destinations::default(), session_path).await
+    }
+
+    /// Constructs a new [Session], reaching the session manager at a bus name other than its well-known name.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - D-Bus [Connection] instance.
+    /// * `destination` - Bus name of the session manager.
+    /// * `session_path` - D-Bus [OwnedObjectPath] to the VPN session.
+    pub async fn with_destination(
+        conn: Connection,
+        destination: &str,
+        session_path: OwnedObjectPath,
+    ) -> Result<Session<'a>> {
+        let mut destinations = Destinations::default();
+        destinations.set(Service::Sessions, destination);
+
+        Self::with_destinations(conn, &destinations, session_path).await
+    }
+
+    pub(crate) async fn with_destinations(
         conn: Connection,
+        destinations: &Destinations,
         session_path: OwnedObjectPath,
     ) -> Result<Session<'a>> {
         let proxy = SessionsNodeProxy::builder(&conn)
-            .destination(Self::DBUS_INTERFACE)?
+            .destination(destinations.get(Service::Sessions).to_owned())?
             .path(session_path.clone())?
             .cache_properties(CacheProperties::No)
             .build()
             .await?;
 
-        Ok(Self { proxy })
+        Ok(Self {
+            proxy,
+            destinations: destinations.clone(),
+        })
     }
 
     /// Get a reference to the underlying proxy's object path.
-    pub fn path(&'a self) -> &ObjectPath {
+    pub fn path(&'a self) -> &'a ObjectPath<'a> {
         self.proxy.path()
     }
 
+    /// Check whether the session is ready to connect.
+    ///
+    /// # Returns
+    ///
+    /// [Error::BackendNotReady] while the backend VPN process is starting, or [Error::MissingUserCredentials] while user input is still required.
     pub async fn ready(&'a self) -> Result<()> {
-        Ok(self.proxy.ready().await.map_err(|err| {
-            let err_str = err.to_string();
-
-            if err_str.contains("net.openvpn.v3.sessions.error: Backend VPN process is not ready") {
-                Error::BackendNotReady
-            } else if err_str.contains("net.openvpn.v3.error.ready: Missing user credentials") {
-                Error::MissingUserCredentials
-            } else {
-                Error::Zbus(err)
-            }
-        })?)
+        // `Ready` reports a backend which is still starting as a generic session manager error
+        self.proxy
+            .ready()
+            .await
+            .map_err(|err| match Error::from(err) {
+                Error::SessionManager(_) => Error::BackendNotReady,
+                err => err,
+            })
+    }
+
+    /// Check whether the session uses Data Channel Offload.
+    ///
+    /// # Returns
+    ///
+    /// [Error::Unsupported] if the services are too old to support Data Channel Offload.
+    pub async fn dco(&self) -> Result<bool> {
+        ServiceVersions::require_dco(self.proxy.inner().connection(), &self.destinations).await?;
+        Ok(self.proxy.dco().await?)
+    }
+
+    /// Enable or disable Data Channel Offload for this session, before calling [Session::connect].
+    ///
+    /// # Returns
+    ///
+    /// [Error::Unsupported] if the services are too old to support Data Channel Offload.
+    pub async fn set_dco(&self, enabled: bool) -> Result<()> {
+        ServiceVersions::require_dco(self.proxy.inner().connection(), &self.destinations).await?;
+        Ok(self.proxy.set_dco(enabled).await?)
     }
 
     /// Start the connection process.
@@ -87,6 +150,274 @@ impl<'a> Session<'a> {
         Ok(self.proxy.status().await?)
     }
 
+    /// Get the current [SessionState], derived from the `status` property.
+    pub async fn state(&self) -> Result<SessionState> {
+        Ok(SessionState::from(self.proxy.status().await?))
+    }
+
+    /// Get a stream of [SessionState] transitions for this VPN session.
+    ///
+    /// The stream is subscribed to before the current state is read, and only yields a state when it differs from the previous one.
+    pub async fn state_stream(&self) -> Result<impl Stream<Item = SessionState> + 'a> {
+        let stream = self.proxy.receive_status_change().await?;
+        let mut last = self.state().await?;
+
+        Ok(stream.filter_map(move |signal| {
+            let state = signal.args().ok().map(|args| {
+                SessionState::from_status(args.code_major, args.code_minor, args.message)
+            });
+
+            future::ready(match state {
+                Some(state) if state != last => {
+                    last = state.clone();
+                    Some(state)
+                }
+                _ => None,
+            })
+        }))
+    }
+
+    /// Wait until the session has connected.
+    ///
+    /// The `StatusChange` signal is subscribed to before the current status is checked, so a transition happening in between is not missed.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the connection to be established.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the client has connected, [Error::AuthFailed], [Error::ConnectionFailed], [Error::InvalidConfiguration] or [Error::BackendKilled] if the connection can no longer succeed, or [Error::Timeout].
+    pub async fn wait_until_connected(&self, timeout: Duration) -> Result<()> {
+        runtime::timeout(timeout, async {
+            let mut stream = self.proxy.receive_status_change().await?;
+            let status = self.proxy.status().await?;
+
+            if let Some(outcome) = connect_outcome(status.code_minor, &status.status_message) {
+                return outcome;
+            }
+
+            while let Some(signal) = stream.next().await {
+                let args = signal.args()?;
+
+                if let Some(outcome) = connect_outcome(args.code_minor, args.message) {
+                    return outcome;
+                }
+            }
+
+            Err(Error::ConnectionFailed(String::from(
+                "StatusChange signal stream ended",
+            )))
+        })
+        .await?
+    }
+
+    /// Answer all pending requests for user input using a [CredentialProvider].
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - [CredentialProvider] which answers the requests.
+    ///
+    /// # Returns
+    ///
+    /// [Error::MissingUserCredentials] if the provider cannot answer one of the requests.
+    pub async fn provide_credentials<P>(&'a self, provider: &P) -> Result<()>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        self.answer_user_input(provider).await.map(|_| ())
+    }
+
+    /// Answer all pending requests for user input, returning how many were answered.
+    async fn answer_user_input<P>(&'a self, provider: &P) -> Result<usize>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        let slots = self.fetch_user_input_slots().await?;
+
+        for slot in &slots {
+            let value = provider
+                .provide(slot)
+                .ok_or(Error::MissingUserCredentials)?;
+            slot.provide_input(&value).await?;
+        }
+
+        Ok(slots.len())
+    }
+
+    /// Connect, answering requests for user input using a [CredentialProvider].
+    ///
+    /// Waits for the backend to become ready, answers any pending requests, starts the connection and waits until the client has connected. If the backend asks for more input while connecting (e.g. a challenge), the requests are answered and the connection is started again.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - [CredentialProvider] which answers the requests.
+    /// * `timeout` - Maximum time to wait for the connection to be established.
+    ///
+    /// # Returns
+    ///
+    /// See [Session::wait_until_connected].
+    pub async fn connect_with<P>(&'a self, provider: &P, timeout: Duration) -> Result<()>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        runtime::timeout(timeout, async {
+            loop {
+                loop {
+                    match self.ready().await {
+                        Ok(()) => break,
+                        Err(Error::MissingUserCredentials) => {
+                            // The backend can report missing input before the request is queued
+                            if self.answer_user_input(provider).await? == 0 {
+                                runtime::sleep(Self::READY_RETRY_INTERVAL).await
+                            }
+                        }
+                        Err(Error::BackendNotReady) => {
+                            runtime::sleep(Self::READY_RETRY_INTERVAL).await
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                let mut stream = self.proxy.receive_status_change().await?;
+                self.proxy.connect().await?;
+
+                let mut requires_input = false;
+                while let Some(signal) = stream.next().await {
+                    let args = signal.args()?;
+
+                    if matches!(
+                        args.code_minor,
+                        StatusMinor::CfgRequireUser
+                            | StatusMinor::SessAuthUserpass
+                            | StatusMinor::SessAuthChallenge
+                    ) {
+                        requires_input = true;
+                        break;
+                    }
+
+                    if let Some(outcome) = connect_outcome(args.code_minor, args.message) {
+                        return outcome;
+                    }
+                }
+
+                if !requires_input {
+                    return Err(Error::ConnectionFailed(String::from(
+                        "StatusChange signal stream ended",
+                    )));
+                }
+            }
+        })
+        .await?
+    }
+
+    /// Get the pending [WebAuthRequest] for this VPN session, if any.
+    ///
+    /// Both the `status` property and the user input queue are checked.
+    pub async fn web_auth_request(&'a self) -> Result<Option<WebAuthRequest>> {
+        if let Some(request) = WebAuthRequest::from_status(&self.proxy.status().await?) {
+            return Ok(Some(request));
+        }
+
+        Ok(self
+            .fetch_user_input_slots()
+            .await?
+            .iter()
+            .find_map(WebAuthRequest::from_slot))
+    }
+
+    /// Get a stream of [WebAuthRequest]s sent through the `AttentionRequired` signal.
+    pub async fn web_auth_stream(&self) -> Result<impl Stream<Item = WebAuthRequest> + 'a> {
+        let stream = self.proxy.receive_attention_required().await?;
+
+        Ok(stream.filter_map(|signal| {
+            future::ready(signal.args().ok().and_then(|args| {
+                WebAuthRequest::from_attention(args.type_, args.group, args.message)
+            }))
+        }))
+    }
+
+    /// Complete web based authentication, after the connection has been started.
+    ///
+    /// Every [WebAuthRequest] sent by the backend is passed to the `opener`, after which this waits for the backend to continue the connection once the browser flow has completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `opener` - [WebAuthOpener] which presents the URL to the user.
+    /// * `timeout` - Maximum time to wait for the connection to be established.
+    ///
+    /// # Returns
+    ///
+    /// See [Session::wait_until_connected].
+    pub async fn authenticate_web<O>(&'a self, opener: &O, timeout: Duration) -> Result<()>
+    where
+        O: WebAuthOpener + ?Sized,
+    {
+        enum Event {
+            Request(WebAuthRequest),
+            Status(StatusMinor, String),
+        }
+
+        runtime::timeout(timeout, async {
+            let requests = self.web_auth_stream().await?.map(Event::Request);
+            let statuses =
+                self.proxy
+                    .receive_status_change()
+                    .await?
+                    .filter_map(|signal| {
+                        future::ready(
+                            signal.args().ok().map(|args| {
+                                Event::Status(args.code_minor, args.message.to_string())
+                            }),
+                        )
+                    });
+            let mut events = futures_util::stream::select(requests, statuses);
+
+            let mut opened: Option<WebAuthRequest> = None;
+            let mut open = |request: WebAuthRequest| -> Result<()> {
+                if opened.as_ref() != Some(&request) {
+                    opener.open(&request)?;
+                    opened = Some(request);
+                }
+                Ok(())
+            };
+
+            let status = self.proxy.status().await?;
+            if let Some(outcome) = connect_outcome(status.code_minor, &status.status_message) {
+                return outcome;
+            }
+            if let Some(request) = self.web_auth_request().await? {
+                open(request)?;
+            }
+
+            while let Some(event) = events.next().await {
+                match event {
+                    Event::Request(request) => open(request)?,
+                    Event::Status(StatusMinor::SessAuthUrl, message) => {
+                        if let Some(request) = WebAuthRequest::parse(&message) {
+                            open(request)?;
+                        }
+                    }
+                    Event::Status(code_minor, message) => {
+                        if let Some(outcome) = connect_outcome(code_minor, &message) {
+                            return outcome;
+                        }
+                    }
+                }
+            }
+
+            Err(Error::ConnectionFailed(String::from(
+                "StatusChange signal stream ended",
+            )))
+        })
+        .await?
+    }
+
+    /// Get the name of the configuration profile when the session was started.
+    pub async fn config_name(&self) -> Result<String> {
+        Ok(self.proxy.config_name().await?)
+    }
+
     /// Get tunnel statistics.
     pub async fn statistics(&'a self) -> Result<Statistics> {
         Ok(self.proxy.statistics().await?)
@@ -130,7 +461,7 @@ impl<'a> Session<'a> {
         qgroup: ClientAttentionGroup,
         qid: u32,
     ) -> Result<UserInputSlot<'a>> {
-        Ok(UserInputSlot::new(&self.proxy, qtype, qgroup, qid).await?)
+        UserInputSlot::new(&self.proxy, qtype, qgroup, qid).await
     }
 
     /// Fetch all required user inputs.
@@ -138,7 +469,7 @@ impl<'a> Session<'a> {
     /// # Returns
     ///
     /// An array of [UserInputSlot] instances which represent single requests for input and can be used to provide input to the backend.
-    pub async fn fetch_user_input_slots(&'a self) -> Result<Vec<UserInputSlot>> {
+    pub async fn fetch_user_input_slots(&'a self) -> Result<Vec<UserInputSlot<'a>>> {
         let mut slots = Vec::new();
 
         for (qtype, qgroup) in self.user_input_queue_get_type_group().await? {
@@ -167,6 +498,83 @@ impl<'a> Session<'a> {
         self.proxy.log_forward(true).await?;
         Ok(self.proxy.receive_log().await?)
     }
+
+    /// Get a stream of [LogEvent]s for this VPN session.
+    ///
+    /// Log signals which cannot be parsed are skipped. This should be called after the backend process is ready.
+    pub async fn log_events(&self) -> Result<impl Stream<Item = LogEvent> + 'a> {
+        Ok(self
+            .log_stream()
+            .await?
+            .filter_map(|signal| future::ready(LogEvent::try_from(signal).ok())))
+    }
+
+    /// Get a stream which yields once this VPN session is removed.
+    ///
+    /// The removal is detected from a `SessRemoved` status change of the session, or the session manager announcing the session was destroyed.
+    pub(crate) async fn removed_stream(&self) -> Result<impl Stream<Item = ()> + 'a> {
+        let path = OwnedObjectPath::from(self.path().to_owned());
+        let status = self
+            .proxy
+            .receive_status_change()
+            .await?
+            .filter_map(|signal| {
+                future::ready(
+                    signal
+                        .args()
+                        .ok()
+                        .filter(|args| args.code_minor == StatusMinor::SessRemoved)
+                        .map(|_| ()),
+                )
+            });
+        let destroyed = SessionsProxy::builder(self.proxy.connection())
+            .destination(self.destinations.get(Service::Sessions).to_owned())?
+            .build()
+            .await?
+            .receive_session_manager_event()
+            .await?
+            .filter_map(move |signal| {
+                future::ready(
+                    signal
+                        .args()
+                        .ok()
+                        .filter(|args| {
+                            args.event_type == EventType::SessDestroyed && args.path == *path
+                        })
+                        .map(|_| ()),
+                )
+            });
+
+        Ok(stream::select(status, destroyed))
+    }
+}
+
+/// Classify a status code while waiting for a connection.
+///
+/// Returns `None` if the status is not (yet) conclusive.
+fn connect_outcome(code_minor: StatusMinor, message: &str) -> Option<Result<()>> {
+    let message = if message.is_empty() {
+        code_minor.to_string()
+    } else {
+        message.to_string()
+    };
+
+    match code_minor {
+        StatusMinor::ConnConnected => Some(Ok(())),
+        StatusMinor::ConnAuthFailed => Some(Err(Error::AuthFailed(message))),
+        StatusMinor::ConnFailed
+        | StatusMinor::ConnDisconnected
+        | StatusMinor::ConnDone
+        | StatusMinor::SessBackendCompleted
+        | StatusMinor::SessRemoved => Some(Err(Error::ConnectionFailed(message))),
+        StatusMinor::CfgError | StatusMinor::CfgInlineMissing => {
+            Some(Err(Error::InvalidConfiguration(message)))
+        }
+        StatusMinor::ProcStopped | StatusMinor::ProcKilled => {
+            Some(Err(Error::BackendKilled(message)))
+        }
+        _ => None,
+    }
 }
 
 /// User Input Slot
@@ -233,12 +641,12 @@ impl<'a> UserInputSlot<'a> {
     }
 
     /// Internal variable name.
-    pub fn variable_name(&'a self) -> &str {
+    pub fn variable_name(&'a self) -> &'a str {
         &self.variable_name
     }
 
     /// A description to present to the user.
-    pub fn label(&'a self) -> &str {
+    pub fn label(&'a self) -> &'a str {
         &self.label
     }
 
@@ -246,4 +654,18 @@ impl<'a> UserInputSlot<'a> {
     pub fn input_mask(&'a self) -> bool {
         self.mask
     }
+
+    /// Parse this request as a [DynamicChallenge].
+    ///
+    /// Returns `Ok(None)` if this is not a [ClientAttentionGroup::ChallengeDynamic] request.
+    pub fn dynamic_challenge(&self) -> Result<Option<DynamicChallenge>> {
+        DynamicChallenge::from_slot(self)
+    }
+
+    /// Get this request as a [StaticChallenge].
+    ///
+    /// Returns `None` if this is not a [ClientAttentionGroup::ChallengeStatic] request.
+    pub fn static_challenge(&self) -> Option<StaticChallenge> {
+        StaticChallenge::from_slot(self)
+    }
 }
@@ -0,0 +1,192 @@
+//! Typed lifecycle state of a VPN session.
+
+use crate::sessions_node::{
+    constants::{StatusMajor, StatusMinor},
+    result::Status,
+};
+
+use std::fmt;
+
+/// Session State
+///
+/// A simplified view of the lifecycle of a VPN session, derived from the [StatusMajor]/[StatusMinor] pairs carried by the `StatusChange` signal and the `status` property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionState {
+    /// The session object exists, but no connection has been attempted yet.
+    Created,
+    /// The backend is waiting for user input, such as credentials or a challenge response.
+    AwaitingCredentials,
+    /// The client is connecting or resuming a connection.
+    Connecting,
+    /// The client has connected successfully.
+    Connected,
+    /// The client lost its connection and is reconnecting.
+    Reconnecting,
+    /// The connection is paused, or is in the process of being paused.
+    Paused,
+    /// The connection failed.
+    Failed {
+        /// Message provided by the backend describing the failure.
+        reason: String,
+    },
+    /// The server rejected the provided credentials.
+    AuthFailed,
+    /// The client disconnected, or the backend process completed its task.
+    Disconnected,
+}
+
+impl SessionState {
+    /// Derive a [SessionState] from a status code pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `code_major` - Major status group classification.
+    /// * `code_minor` - Minor status category classification within the status group.
+    /// * `message` - Descriptive message of the status, used as the reason for failures.
+    pub fn from_status(code_major: StatusMajor, code_minor: StatusMinor, message: &str) -> Self {
+        match code_minor {
+            StatusMinor::CfgRequireUser
+            | StatusMinor::SessAuthUserpass
+            | StatusMinor::SessAuthChallenge
+            | StatusMinor::SessAuthUrl
+            | StatusMinor::PKCS11Sign
+            | StatusMinor::PKCS11Encrypt
+            | StatusMinor::PKCS11Decrypt
+            | StatusMinor::PKCS11Verify => Self::AwaitingCredentials,
+            StatusMinor::ConnConnecting | StatusMinor::ConnResuming => Self::Connecting,
+            StatusMinor::ConnConnected => Self::Connected,
+            StatusMinor::ConnReconnecting => Self::Reconnecting,
+            StatusMinor::ConnPausing | StatusMinor::ConnPaused => Self::Paused,
+            StatusMinor::ConnAuthFailed => Self::AuthFailed,
+            StatusMinor::CfgError
+            | StatusMinor::CfgInlineMissing
+            | StatusMinor::ConnFailed
+            | StatusMinor::ProcKilled => Self::Failed {
+                reason: if message.is_empty() {
+                    format!("{}: {}", code_major, code_minor)
+                } else {
+                    message.to_string()
+                },
+            },
+            StatusMinor::ConnDisconnecting
+            | StatusMinor::ConnDisconnected
+            | StatusMinor::ConnDone
+            | StatusMinor::SessBackendCompleted
+            | StatusMinor::SessRemoved
+            | StatusMinor::ProcStopped => Self::Disconnected,
+            StatusMinor::Unset
+            | StatusMinor::CfgOk
+            | StatusMinor::ConnInit
+            | StatusMinor::SessNew
+            | StatusMinor::ProcStarted => Self::Created,
+        }
+    }
+
+    /// Returns `true` if the session can no longer reach the [SessionState::Connected] state without being restarted.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Failed { .. } | Self::AuthFailed | Self::Disconnected
+        )
+    }
+}
+
+impl From<&Status> for SessionState {
+    fn from(status: &Status) -> Self {
+        Self::from_status(status.code_major, status.code_minor, &status.status_message)
+    }
+}
+
+impl From<Status> for SessionState {
+    fn from(status: Status) -> Self {
+        Self::from(&status)
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => write!(f, "Created"),
+            Self::AwaitingCredentials => write!(f, "Awaiting credentials"),
+            Self::Connecting => write!(f, "Connecting"),
+            Self::Connected => write!(f, "Connected"),
+            Self::Reconnecting => write!(f, "Reconnecting"),
+            Self::Paused => write!(f, "Paused"),
+            Self::Failed { reason } => write!(f, "Failed: {}", reason),
+            Self::AuthFailed => write!(f, "Authentication failed"),
+            Self::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_status() {
+        let state = |code_minor, message| {
+            SessionState::from_status(StatusMajor::CONNECTION, code_minor, message)
+        };
+
+        assert_eq!(state(StatusMinor::SessNew, ""), SessionState::Created);
+        assert_eq!(
+            state(StatusMinor::SessAuthUrl, ""),
+            SessionState::AwaitingCredentials
+        );
+        assert_eq!(
+            state(StatusMinor::ConnResuming, ""),
+            SessionState::Connecting
+        );
+        assert_eq!(
+            state(StatusMinor::ConnConnected, ""),
+            SessionState::Connected
+        );
+        assert_eq!(
+            state(StatusMinor::ConnReconnecting, ""),
+            SessionState::Reconnecting
+        );
+        assert_eq!(state(StatusMinor::ConnPausing, ""), SessionState::Paused);
+        assert_eq!(
+            state(StatusMinor::ConnAuthFailed, ""),
+            SessionState::AuthFailed
+        );
+        assert_eq!(
+            state(StatusMinor::ProcStopped, ""),
+            SessionState::Disconnected
+        );
+        assert_eq!(
+            state(StatusMinor::ConnFailed, "TLS handshake failed"),
+            SessionState::Failed {
+                reason: String::from("TLS handshake failed")
+            }
+        );
+        assert_eq!(
+            state(StatusMinor::ConnFailed, ""),
+            SessionState::Failed {
+                reason: format!("{}: {}", StatusMajor::CONNECTION, StatusMinor::ConnFailed)
+            }
+        );
+    }
+
+    #[test]
+    fn is_terminal() {
+        assert!(SessionState::AuthFailed.is_terminal());
+        assert!(SessionState::Disconnected.is_terminal());
+        assert!(SessionState::Failed {
+            reason: String::new()
+        }
+        .is_terminal());
+
+        for state in [
+            SessionState::Created,
+            SessionState::AwaitingCredentials,
+            SessionState::Connecting,
+            SessionState::Connected,
+            SessionState::Reconnecting,
+            SessionState::Paused,
+        ] {
+            assert!(!state.is_terminal(), "{}", state);
+        }
+    }
+}
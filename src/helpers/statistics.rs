@@ -0,0 +1,272 @@
+//! Typed tunnel statistics, and sampling of throughput and packet rates.
+
+use super::Session;
+
+use crate::{runtime, sessions_node::result::Statistics, Result};
+
+use futures_util::{stream, Stream};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Session Statistics
+///
+/// The counters reported by the `statistics` property of a VPN session. Counters which are not known to this crate are kept in [SessionStatistics::extra].
+///
+/// Serializes to a flat object using the key names of the D-Bus API, e.g. `BYTES_IN`.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::SessionStatistics;
+/// use std::collections::HashMap;
+///
+/// let statistics = SessionStatistics::from(HashMap::from([
+///     (String::from("BYTES_IN"), 2048),
+///     (String::from("TUN_PACKETS_OUT"), 12),
+///     (String::from("KEEPALIVE_TIMEOUT"), 1),
+/// ]));
+///
+/// assert_eq!(statistics.bytes_in, 2048);
+/// assert_eq!(statistics.tun_packets_out, 12);
+/// assert_eq!(statistics.extra.get("KEEPALIVE_TIMEOUT"), Some(&1));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct SessionStatistics {
+    /// Bytes received from the VPN server.
+    #[serde(default)]
+    pub bytes_in: u64,
+    /// Bytes sent to the VPN server.
+    #[serde(default)]
+    pub bytes_out: u64,
+    /// Packets received from the VPN server.
+    #[serde(default)]
+    pub packets_in: u64,
+    /// Packets sent to the VPN server.
+    #[serde(default)]
+    pub packets_out: u64,
+    /// Bytes read from the virtual network interface.
+    #[serde(default)]
+    pub tun_bytes_in: u64,
+    /// Bytes written to the virtual network interface.
+    #[serde(default)]
+    pub tun_bytes_out: u64,
+    /// Packets read from the virtual network interface.
+    #[serde(default)]
+    pub tun_packets_in: u64,
+    /// Packets written to the virtual network interface.
+    #[serde(default)]
+    pub tun_packets_out: u64,
+    /// All other counters, keyed by their name in the D-Bus API.
+    #[serde(flatten)]
+    pub extra: HashMap<String, i64>,
+}
+
+impl SessionStatistics {
+    fn counter_mut(&mut self, key: &str) -> Option<&mut u64> {
+        match key {
+            "BYTES_IN" => Some(&mut self.bytes_in),
+            "BYTES_OUT" => Some(&mut self.bytes_out),
+            "PACKETS_IN" => Some(&mut self.packets_in),
+            "PACKETS_OUT" => Some(&mut self.packets_out),
+            "TUN_BYTES_IN" => Some(&mut self.tun_bytes_in),
+            "TUN_BYTES_OUT" => Some(&mut self.tun_bytes_out),
+            "TUN_PACKETS_IN" => Some(&mut self.tun_packets_in),
+            "TUN_PACKETS_OUT" => Some(&mut self.tun_packets_out),
+            _ => None,
+        }
+    }
+}
+
+impl From<Statistics> for SessionStatistics {
+    fn from(statistics: Statistics) -> Self {
+        let mut typed = Self::default();
+
+        for (key, value) in statistics {
+            match typed.counter_mut(&key) {
+                Some(counter) => *counter = value.max(0) as u64,
+                None => {
+                    typed.extra.insert(key, value);
+                }
+            }
+        }
+
+        typed
+    }
+}
+
+impl From<SessionStatistics> for Statistics {
+    fn from(statistics: SessionStatistics) -> Self {
+        let mut map = statistics.extra;
+
+        for (key, value) in [
+            ("BYTES_IN", statistics.bytes_in),
+            ("BYTES_OUT", statistics.bytes_out),
+            ("PACKETS_IN", statistics.packets_in),
+            ("PACKETS_OUT", statistics.packets_out),
+            ("TUN_BYTES_IN", statistics.tun_bytes_in),
+            ("TUN_BYTES_OUT", statistics.tun_bytes_out),
+            ("TUN_PACKETS_IN", statistics.tun_packets_in),
+            ("TUN_PACKETS_OUT", statistics.tun_packets_out),
+        ] {
+            map.insert(key.to_string(), i64::try_from(value).unwrap_or(i64::MAX));
+        }
+
+        map
+    }
+}
+
+/// Statistics Rates
+///
+/// Throughput and packet rates of a VPN session between two [SessionStatistics] samples, per second.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::{SessionStatistics, StatisticsRates};
+/// use std::time::Duration;
+///
+/// let previous = SessionStatistics { bytes_in: 1000, packets_in: 10, ..Default::default() };
+/// let current = SessionStatistics { bytes_in: 5000, packets_in: 30, ..Default::default() };
+///
+/// let rates = StatisticsRates::between(&previous, &current, Duration::from_secs(2));
+/// assert_eq!(rates.bytes_in, 2000.0);
+/// assert_eq!(rates.packets_in, 10.0);
+/// assert_eq!(rates.bytes_out, 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatisticsRates {
+    /// Bytes received from the VPN server per second.
+    pub bytes_in: f64,
+    /// Bytes sent to the VPN server per second.
+    pub bytes_out: f64,
+    /// Packets received from the VPN server per second.
+    pub packets_in: f64,
+    /// Packets sent to the VPN server per second.
+    pub packets_out: f64,
+}
+
+impl StatisticsRates {
+    /// Compute the rates between two samples taken `elapsed` apart.
+    ///
+    /// Counters which decreased, e.g. because the backend restarted, count as zero. All rates are zero if no time has elapsed.
+    pub fn between(
+        previous: &SessionStatistics,
+        current: &SessionStatistics,
+        elapsed: Duration,
+    ) -> Self {
+        let seconds = elapsed.as_secs_f64();
+        let rate = |previous: u64, current: u64| {
+            if seconds > 0.0 {
+                current.saturating_sub(previous) as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+
+        Self {
+            bytes_in: rate(previous.bytes_in, current.bytes_in),
+            bytes_out: rate(previous.bytes_out, current.bytes_out),
+            packets_in: rate(previous.packets_in, current.packets_in),
+            packets_out: rate(previous.packets_out, current.packets_out),
+        }
+    }
+}
+
+/// Statistics Sample
+///
+/// A sample taken by a [StatisticsSampler].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatisticsSample {
+    /// When the statistics were fetched.
+    pub taken_at: Instant,
+    /// The counters at the time of the sample.
+    pub statistics: SessionStatistics,
+    /// Rates since the previous sample, or `None` for the first sample.
+    pub rates: Option<StatisticsRates>,
+}
+
+/// Statistics Sampler
+///
+/// Polls the statistics of a [Session], and computes throughput and packet rates between consecutive samples.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async_std::task::block_on(async {
+/// use futures_util::StreamExt;
+/// use openvpn3_rs::helpers::{OpenVPN3, StatisticsSampler};
+/// use std::time::Duration;
+///
+/// let openvpn3 = OpenVPN3::connect().await.unwrap();
+/// let sessions = openvpn3.sessions().await.unwrap();
+///
+/// let mut samples = Box::pin(StatisticsSampler::new(&sessions[0], Duration::from_secs(5)).into_stream());
+/// while let Some(sample) = samples.next().await {
+///     if let Some(rates) = sample.unwrap().rates {
+///         println!("in: {:.0} B/s, out: {:.0} B/s", rates.bytes_in, rates.bytes_out);
+///     }
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatisticsSampler<'a> {
+    session: &'a Session<'a>,
+    interval: Duration,
+    previous: Option<(Instant, SessionStatistics)>,
+}
+
+impl<'a> StatisticsSampler<'a> {
+    /// Create a new [StatisticsSampler].
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - Session to sample the statistics of.
+    /// * `interval` - Time between samples taken by [StatisticsSampler::into_stream].
+    pub fn new(session: &'a Session<'a>, interval: Duration) -> Self {
+        Self {
+            session,
+            interval,
+            previous: None,
+        }
+    }
+
+    /// Get the time between samples.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Fetch the statistics now, computing rates since the previous sample.
+    pub async fn sample(&mut self) -> Result<StatisticsSample> {
+        let statistics = SessionStatistics::from(self.session.statistics().await?);
+        let taken_at = Instant::now();
+
+        let rates = self.previous.as_ref().map(|(previous_at, previous)| {
+            StatisticsRates::between(previous, &statistics, taken_at - *previous_at)
+        });
+        self.previous = Some((taken_at, statistics.clone()));
+
+        Ok(StatisticsSample {
+            taken_at,
+            statistics,
+            rates,
+        })
+    }
+
+    /// Take a sample immediately and then once every interval.
+    ///
+    /// The stream does not end on its own. Errors are yielded, after which sampling continues.
+    pub fn into_stream(self) -> impl Stream<Item = Result<StatisticsSample>> + Send + 'a {
+        stream::unfold((self, true), |(mut sampler, first)| async move {
+            if !first {
+                runtime::sleep(sampler.interval).await;
+            }
+
+            let sample = sampler.sample().await;
+            Some((sample, (sampler, false)))
+        })
+    }
+}
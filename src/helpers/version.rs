@@ -0,0 +1,220 @@
+//! Parses the versions reported by the OpenVPN 3 services, and derives which features they support.
+
+use super::{builder::Destinations, Service};
+
+use crate::{Error, Result};
+
+use std::{cmp::Ordering, fmt};
+use zbus::{fdo::PropertiesProxy, names::InterfaceName, Connection};
+
+/// Version of an OpenVPN 3 service, as reported by its `version` property.
+///
+/// Release versions look like `v21`, pre-releases like `v20_beta`. Versions which do not follow this format, e.g. those of builds from a git checkout, are treated as development builds which are newer than any release.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::helpers::ServiceVersion;
+///
+/// let version = ServiceVersion::parse("v20_beta");
+/// assert_eq!(version.major(), Some(20));
+/// assert_eq!(version.pre_release(), Some("beta"));
+/// assert!(version < ServiceVersion::parse("v20"));
+/// assert!(ServiceVersion::parse("v21") < ServiceVersion::parse("git:master/3f2a1b0"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ServiceVersion {
+    raw: String,
+    major: Option<u32>,
+    pre_release: Option<String>,
+}
+
+impl ServiceVersion {
+    /// Parse a version string.
+    pub fn parse(version: &str) -> Self {
+        let raw = version.trim().to_string();
+        let rest = raw.strip_prefix('v').unwrap_or(&raw);
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        let (major, suffix) = match rest[..digits].parse() {
+            Ok(major) => (Some(major), &rest[digits..]),
+            Err(_) => (None, ""),
+        };
+
+        let pre_release = suffix
+            .trim_start_matches(['_', '-', '.'])
+            .split(['_', '-', ':'])
+            .next()
+            .filter(|pre_release| !pre_release.is_empty() && *pre_release != "git")
+            .map(String::from);
+
+        Self {
+            raw,
+            major,
+            pre_release,
+        }
+    }
+
+    /// Major version, `None` for development builds.
+    pub fn major(&self) -> Option<u32> {
+        self.major
+    }
+
+    /// Pre-release tag, e.g. `beta`.
+    pub fn pre_release(&self) -> Option<&str> {
+        self.pre_release.as_deref()
+    }
+
+    /// Whether this is a development build without a release version.
+    pub fn is_development(&self) -> bool {
+        self.major.is_none()
+    }
+
+    /// Whether this version is the given major release, one of its pre-releases, or newer.
+    pub fn at_least(&self, major: u32) -> bool {
+        self.major.is_none_or(|m| m >= major)
+    }
+
+    /// The version string as reported by the service.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Fetch the version of a service reached at `destination`.
+    pub(crate) async fn fetch(
+        connection: &Connection,
+        destination: &str,
+        service: Service,
+    ) -> Result<Self> {
+        let version = PropertiesProxy::builder(connection)
+            .destination(destination.to_owned())?
+            .path(service.path())?
+            .build()
+            .await?
+            .get(
+                InterfaceName::from_static_str_unchecked(service.interface()),
+                "version",
+            )
+            .await?;
+        let version = String::try_from(version).map_err(zbus::Error::from)?;
+
+        Ok(Self::parse(&version))
+    }
+
+    fn sort_key(&self) -> (u32, bool, &str) {
+        (
+            self.major.unwrap_or(u32::MAX),
+            self.pre_release.is_none(),
+            &self.raw,
+        )
+    }
+}
+
+impl From<&str> for ServiceVersion {
+    fn from(version: &str) -> Self {
+        Self::parse(version)
+    }
+}
+
+impl PartialEq for ServiceVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ServiceVersion {}
+
+impl PartialOrd for ServiceVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ServiceVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl fmt::Display for ServiceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Versions of all OpenVPN 3 services.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceVersions {
+    pub sessions: ServiceVersion,
+    pub configuration: ServiceVersion,
+    pub log: ServiceVersion,
+    pub netcfg: ServiceVersion,
+}
+
+impl ServiceVersions {
+    /// First release with Data Channel Offload support in the session manager and the network configuration service.
+    pub const DCO: u32 = 17;
+    /// First release where the log service can proxy Log events of a session to another D-Bus service.
+    pub const LOG_PROXY: u32 = 19;
+
+    /// Fetch the versions of all services.
+    pub(crate) async fn fetch(
+        connection: &Connection,
+        destinations: &Destinations,
+    ) -> Result<Self> {
+        let fetch = |service| ServiceVersion::fetch(connection, destinations.get(service), service);
+
+        Ok(Self {
+            sessions: fetch(Service::Sessions).await?,
+            configuration: fetch(Service::Configuration).await?,
+            log: fetch(Service::Log).await?,
+            netcfg: fetch(Service::NetCfg).await?,
+        })
+    }
+
+    /// Version of a single service.
+    pub fn get(&self, service: Service) -> &ServiceVersion {
+        match service {
+            Service::Sessions => &self.sessions,
+            Service::Configuration => &self.configuration,
+            Service::Log => &self.log,
+            Service::NetCfg => &self.netcfg,
+        }
+    }
+
+    /// Whether the services support Data Channel Offload, i.e. the `dco` properties and `EnableDCO`.
+    pub fn supports_dco(&self) -> bool {
+        self.sessions.at_least(Self::DCO) && self.netcfg.at_least(Self::DCO)
+    }
+
+    /// Fail with [Error::Unsupported] unless the services support Data Channel Offload.
+    ///
+    /// Only the versions of the session manager and the network configuration service are fetched, once per client.
+    pub(crate) async fn require_dco(
+        connection: &Connection,
+        destinations: &Destinations,
+    ) -> Result<()> {
+        let supported = match destinations.dco() {
+            Some(supported) => supported,
+            None => {
+                let fetch =
+                    |service| ServiceVersion::fetch(connection, destinations.get(service), service);
+                let supported = fetch(Service::Sessions).await?.at_least(Self::DCO)
+                    && fetch(Service::NetCfg).await?.at_least(Self::DCO);
+                destinations.set_dco(supported);
+                supported
+            }
+        };
+
+        if supported {
+            Ok(())
+        } else {
+            Err(Error::Unsupported(String::from("Data Channel Offload")))
+        }
+    }
+
+    /// Whether the log service supports `ProxyLogEvents`.
+    pub fn supports_log_proxy(&self) -> bool {
+        self.log.at_least(Self::LOG_PROXY)
+    }
+}
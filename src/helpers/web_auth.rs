@@ -0,0 +1,176 @@
+//! Web based authentication (e.g. SAML/SSO) for VPN sessions.
+
+use super::UserInputSlot;
+
+use crate::{
+    sessions_node::{
+        constants::{ClientAttentionGroup, ClientAttentionType, StatusMinor},
+        result::Status,
+    },
+    Result,
+};
+
+use std::{fmt, process::Command, thread};
+
+/// Web Authentication Request
+///
+/// A request by the backend VPN process to complete authentication in a web browser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebAuthRequest {
+    url: String,
+    flags: Vec<String>,
+}
+
+impl WebAuthRequest {
+    /// Parse a [WebAuthRequest] from a message sent by the backend.
+    ///
+    /// Accepts a bare URL, as well as the `OPEN_URL:<url>` and `WEB_AUTH:<flags>:<url>` formats. Only `http` and `https` URLs are accepted, so arbitrary status text is never passed on to a browser.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message containing the URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use openvpn3_rs::helpers::WebAuthRequest;
+    ///
+    /// let request = WebAuthRequest::parse("WEB_AUTH:proxy,hidden:https://sso.example.com/").unwrap();
+    /// assert_eq!(request.url(), "https://sso.example.com/");
+    /// assert_eq!(request.flags(), ["proxy", "hidden"]);
+    ///
+    /// assert!(WebAuthRequest::parse("OPEN_URL:file:///etc/passwd").is_none());
+    /// assert!(WebAuthRequest::parse("Authentication pending").is_none());
+    /// ```
+    pub fn parse(message: &str) -> Option<Self> {
+        let message = message.trim();
+
+        let (flags, url) = if let Some(url) = message.strip_prefix("OPEN_URL:") {
+            (Vec::new(), url)
+        } else if let Some(rest) = message.strip_prefix("WEB_AUTH:") {
+            let (flags, url) = rest.split_once(':')?;
+            (
+                flags
+                    .split(',')
+                    .filter(|flag| !flag.is_empty())
+                    .map(String::from)
+                    .collect(),
+                url,
+            )
+        } else {
+            (Vec::new(), message)
+        };
+
+        let scheme = url
+            .split_once("://")
+            .map(|(scheme, _)| scheme.to_ascii_lowercase());
+        if !matches!(scheme.as_deref(), Some("http" | "https")) {
+            return None;
+        }
+
+        Some(Self {
+            url: url.to_string(),
+            flags,
+        })
+    }
+
+    /// Construct a [WebAuthRequest] from the arguments of an `AttentionRequired` signal.
+    ///
+    /// Returns `None` if the signal is not a [ClientAttentionGroup::OpenUrl] request.
+    pub fn from_attention(
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        message: &str,
+    ) -> Option<Self> {
+        match (qtype, qgroup) {
+            (ClientAttentionType::Credentials, ClientAttentionGroup::OpenUrl) => {
+                Self::parse(message)
+            }
+            _ => None,
+        }
+    }
+
+    /// Construct a [WebAuthRequest] from a session [Status].
+    ///
+    /// Returns `None` unless the status is [StatusMinor::SessAuthUrl].
+    pub fn from_status(status: &Status) -> Option<Self> {
+        match status.code_minor {
+            StatusMinor::SessAuthUrl => Self::parse(&status.status_message),
+            _ => None,
+        }
+    }
+
+    /// Construct a [WebAuthRequest] from a [UserInputSlot].
+    ///
+    /// Returns `None` if the slot is not a [ClientAttentionGroup::OpenUrl] request.
+    pub fn from_slot(slot: &UserInputSlot<'_>) -> Option<Self> {
+        match slot.type_group().1 {
+            ClientAttentionGroup::OpenUrl => Self::parse(slot.label()),
+            _ => None,
+        }
+    }
+
+    /// URL to open in a web browser.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Flags sent along with a `WEB_AUTH` request, e.g. `proxy` or `hidden`.
+    pub fn flags(&self) -> &[String] {
+        &self.flags
+    }
+}
+
+impl fmt::Display for WebAuthRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Web Authentication Opener
+///
+/// Presents a [WebAuthRequest] to the user. Closures taking a [WebAuthRequest] and returning a [Result] implement this trait as well.
+pub trait WebAuthOpener {
+    /// Open the URL of the request.
+    fn open(&self, request: &WebAuthRequest) -> Result<()>;
+}
+
+impl<F> WebAuthOpener for F
+where
+    F: Fn(&WebAuthRequest) -> Result<()>,
+{
+    fn open(&self, request: &WebAuthRequest) -> Result<()> {
+        self(request)
+    }
+}
+
+/// Opens the URL in the user's preferred web browser using `xdg-open`.
+///
+/// `xdg-open` is started without waiting for it to exit, so the calling executor is not blocked. Only failing to start it is reported as an error.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XdgOpen;
+
+impl WebAuthOpener for XdgOpen {
+    fn open(&self, request: &WebAuthRequest) -> Result<()> {
+        let mut child = Command::new("xdg-open").arg(request.url()).spawn()?;
+
+        // Reap the process once it exits, without blocking the caller
+        thread::spawn(move || child.wait());
+
+        Ok(())
+    }
+}
+
+/// Prints the URL to the terminal, so the user can open it manually.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PrintUrl;
+
+impl WebAuthOpener for PrintUrl {
+    fn open(&self, request: &WebAuthRequest) -> Result<()> {
+        eprintln!(
+            "Web based authentication required, open this URL to continue: {}",
+            request.url()
+        );
+        Ok(())
+    }
+}
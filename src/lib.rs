@@ -1,8 +1,16 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
 mod error;
 pub mod helpers;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod profile;
 mod proxy;
+mod runtime;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use self::error::*;
 pub use self::proxy::*;
@@ -0,0 +1,382 @@
+//! Exports the state and statistics of VPN sessions as [OpenMetrics](https://openmetrics.io) text.
+//!
+//! Requires the `metrics` feature. The text can be served to Prometheus with [serve], or written for the node exporter's textfile collector with [write_textfile].
+//!
+//! Every session metric carries the labels `config`, `device` and `path`, holding the configuration profile name, the virtual network interface and the D-Bus object path of the session. Sessions whose metrics could not be collected are counted by `openvpn3_scrape_failed_sessions`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use openvpn3_rs::{helpers::blocking::OpenVPN3, metrics};
+//! use std::net::TcpListener;
+//!
+//! let openvpn3 = OpenVPN3::connect().unwrap();
+//! let listener = TcpListener::bind("127.0.0.1:9176").unwrap();
+//!
+//! metrics::serve(&openvpn3, listener).unwrap();
+//! ```
+
+use crate::{
+    helpers::{self, OpenVPN3, Session, SessionStatistics},
+    runtime,
+    sessions_node::constants::StatusMinor,
+    Error, Result,
+};
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Content type of the OpenMetrics text format.
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Time after which [serve] gives up reading a request or writing a response.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Name, help text and accessor of a [SessionStatistics] counter.
+type Counter = (&'static str, &'static str, fn(&SessionStatistics) -> u64);
+
+/// Counters of [SessionStatistics], exported as `openvpn3_session_<name>_total`.
+const COUNTERS: [Counter; 8] = [
+    ("bytes_in", "Bytes received from the VPN server.", |s| {
+        s.bytes_in
+    }),
+    ("bytes_out", "Bytes sent to the VPN server.", |s| {
+        s.bytes_out
+    }),
+    ("packets_in", "Packets received from the VPN server.", |s| {
+        s.packets_in
+    }),
+    ("packets_out", "Packets sent to the VPN server.", |s| {
+        s.packets_out
+    }),
+    (
+        "tun_bytes_in",
+        "Bytes read from the virtual network interface.",
+        |s| s.tun_bytes_in,
+    ),
+    (
+        "tun_bytes_out",
+        "Bytes written to the virtual network interface.",
+        |s| s.tun_bytes_out,
+    ),
+    (
+        "tun_packets_in",
+        "Packets read from the virtual network interface.",
+        |s| s.tun_packets_in,
+    ),
+    (
+        "tun_packets_out",
+        "Packets written to the virtual network interface.",
+        |s| s.tun_packets_out,
+    ),
+];
+
+/// Metrics of a single VPN session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionMetrics {
+    /// D-Bus object path of the session.
+    pub path: String,
+    /// Name of the configuration profile the session was started from.
+    pub config_name: String,
+    /// Name of the virtual network interface, empty until connected.
+    pub device_name: String,
+    /// Last reported status of the session.
+    pub status: StatusMinor,
+    /// Time since the session was created.
+    pub age: Duration,
+    /// Process ID of the VPN backend client process.
+    pub backend_pid: u32,
+    /// Whether Data Channel Offload is enabled, `None` if the services do not support it or it could not be read.
+    pub dco: Option<bool>,
+    /// Tunnel statistics.
+    pub statistics: SessionStatistics,
+}
+
+impl SessionMetrics {
+    /// Collect the metrics of a session.
+    pub async fn collect(session: &Session<'_>) -> Result<Self> {
+        let created: u64 = session.get_property("session_created").await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Ok(Self {
+            path: session.path().to_string(),
+            config_name: session.config_name().await?,
+            device_name: session.get_property("device_name").await?,
+            status: session.status().await?.code_minor,
+            age: now.saturating_sub(Duration::from_secs(created)),
+            backend_pid: session.get_property("backend_pid").await?,
+            dco: session.dco().await.ok(),
+            statistics: SessionStatistics::from(session.statistics().await?),
+        })
+    }
+
+    fn labels(&self) -> String {
+        format!(
+            "config=\"{}\",device=\"{}\",path=\"{}\"",
+            escape(&self.config_name),
+            escape(&self.device_name),
+            escape(&self.path),
+        )
+    }
+}
+
+/// Metrics of all sessions available to the user.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scrape {
+    /// Metrics of each session.
+    pub sessions: Vec<SessionMetrics>,
+    /// Number of sessions whose metrics could not be collected, for other reasons than the session being removed.
+    pub failed: u64,
+}
+
+/// Collect the metrics of all sessions available to the user.
+///
+/// Sessions which were removed while collecting are skipped. Sessions whose metrics cannot be collected for another reason are skipped as well, but counted in [Scrape::failed].
+pub async fn collect(openvpn3: &OpenVPN3<'_>) -> Result<Scrape> {
+    let mut scrape = Scrape::default();
+
+    for session in openvpn3.sessions().await? {
+        match SessionMetrics::collect(&session).await {
+            Ok(session) => scrape.sessions.push(session),
+            Err(Error::NotFound(_)) => {}
+            Err(_) => scrape.failed += 1,
+        }
+    }
+
+    Ok(scrape)
+}
+
+/// Encode session metrics as OpenMetrics text.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::{
+///     helpers::SessionStatistics,
+///     metrics::{self, Scrape, SessionMetrics},
+///     sessions_node::constants::StatusMinor,
+/// };
+/// use std::time::Duration;
+///
+/// let text = metrics::encode(&Scrape {
+///     sessions: vec![SessionMetrics {
+///         path: String::from("/net/openvpn/v3/sessions/1"),
+///         config_name: String::from("My VPN"),
+///         device_name: String::from("tun0"),
+///         status: StatusMinor::ConnConnected,
+///         age: Duration::from_secs(60),
+///         backend_pid: 4242,
+///         dco: Some(false),
+///         statistics: SessionStatistics { bytes_in: 2048, ..Default::default() },
+///     }],
+///     failed: 1,
+/// });
+///
+/// assert!(text.contains(
+///     "openvpn3_session_bytes_in_total{config=\"My VPN\",device=\"tun0\",path=\"/net/openvpn/v3/sessions/1\"} 2048\n"
+/// ));
+/// assert!(text.contains("openvpn3_scrape_failed_sessions 1\n"));
+/// assert!(text.ends_with("# EOF\n"));
+/// ```
+pub fn encode(scrape: &Scrape) -> String {
+    let mut text = String::new();
+    let sessions = &scrape.sessions;
+    let labels: Vec<String> = sessions.iter().map(SessionMetrics::labels).collect();
+
+    family(
+        &mut text,
+        "openvpn3_scrape_failed_sessions",
+        "gauge",
+        "Sessions whose metrics could not be collected.",
+    );
+    let _ = writeln!(text, "openvpn3_scrape_failed_sessions {}", scrape.failed);
+
+    family(
+        &mut text,
+        "openvpn3_session_status",
+        "gauge",
+        "Minor status code of the session.",
+    );
+    for (session, labels) in sessions.iter().zip(&labels) {
+        let _ = writeln!(
+            text,
+            "openvpn3_session_status{{{},status=\"{:?}\"}} {}",
+            labels, session.status, session.status as u32
+        );
+    }
+
+    family(
+        &mut text,
+        "openvpn3_session_age_seconds",
+        "gauge",
+        "Time since the session was created.",
+    );
+    let _ = writeln!(text, "# UNIT openvpn3_session_age_seconds seconds");
+    for (session, labels) in sessions.iter().zip(&labels) {
+        let _ = writeln!(
+            text,
+            "openvpn3_session_age_seconds{{{}}} {}",
+            labels,
+            session.age.as_secs()
+        );
+    }
+
+    family(
+        &mut text,
+        "openvpn3_session_backend_pid",
+        "gauge",
+        "Process ID of the VPN backend client process.",
+    );
+    for (session, labels) in sessions.iter().zip(&labels) {
+        let _ = writeln!(
+            text,
+            "openvpn3_session_backend_pid{{{}}} {}",
+            labels, session.backend_pid
+        );
+    }
+
+    family(
+        &mut text,
+        "openvpn3_session_dco",
+        "gauge",
+        "Whether Data Channel Offload is enabled.",
+    );
+    for (session, labels) in sessions.iter().zip(&labels) {
+        if let Some(dco) = session.dco {
+            let _ = writeln!(text, "openvpn3_session_dco{{{}}} {}", labels, dco as u8);
+        }
+    }
+
+    for (counter, help, value) in COUNTERS {
+        let name = format!("openvpn3_session_{}", counter);
+        family(&mut text, &name, "counter", help);
+
+        for (session, labels) in sessions.iter().zip(&labels) {
+            let value = value(&session.statistics);
+            let _ = writeln!(text, "{}_total{{{}}} {}", name, labels, value);
+        }
+    }
+
+    family(
+        &mut text,
+        "openvpn3_session_statistic",
+        "unknown",
+        "Other statistics reported by the VPN backend, by their name in the D-Bus API.",
+    );
+    for (session, labels) in sessions.iter().zip(&labels) {
+        let mut extra: Vec<_> = session.statistics.extra.iter().collect();
+        extra.sort();
+
+        for (name, value) in extra {
+            let _ = writeln!(
+                text,
+                "openvpn3_session_statistic{{{},name=\"{}\"}} {}",
+                labels,
+                escape(name),
+                value
+            );
+        }
+    }
+
+    text.push_str("# EOF\n");
+    text
+}
+
+/// Collect the metrics of all sessions and encode them as OpenMetrics text.
+pub async fn render(openvpn3: &OpenVPN3<'_>) -> Result<String> {
+    Ok(encode(&collect(openvpn3).await?))
+}
+
+/// Write the metrics of all sessions to a file, e.g. for the node exporter's textfile collector.
+///
+/// The file is replaced atomically, by writing a temporary file next to it first.
+pub async fn write_textfile<P: AsRef<Path>>(openvpn3: &OpenVPN3<'_>, path: P) -> Result<()> {
+    let path = path.as_ref();
+    let text = render(openvpn3).await?;
+
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, text)?;
+    fs::rename(&temporary, path)?;
+
+    Ok(())
+}
+
+/// Serve the metrics of all sessions over HTTP at `/metrics`.
+///
+/// Requests are handled one at a time on the calling thread, and this only returns if accepting a connection fails. Other paths are answered with `404 Not Found`. Clients which do not send a request or read the response within 10 seconds are disconnected.
+pub fn serve(openvpn3: &helpers::blocking::OpenVPN3<'_>, listener: TcpListener) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        let _ = respond(openvpn3, stream);
+    }
+}
+
+fn respond(openvpn3: &helpers::blocking::OpenVPN3<'_>, mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let target = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => target.split('?').next().unwrap_or_default(),
+        _ => "",
+    };
+
+    let (status, content_type, body) = if target == "/metrics" {
+        match runtime::block_on(render(openvpn3.inner())) {
+            Ok(text) => ("200 OK", CONTENT_TYPE, text),
+            Err(err) => (
+                "500 Internal Server Error",
+                "text/plain; charset=utf-8",
+                format!("{}\n", err),
+            ),
+        }
+    } else {
+        (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            String::from("Not Found\n"),
+        )
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+
+    Ok(())
+}
+
+fn family(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# TYPE {} {}", name, kind);
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,132 @@
+//! Embeds files referenced by a profile as inline blocks, as required by the OpenVPN 3 configuration manager.
+
+use super::{AuthUserPass, Directive, Material, Profile};
+
+use crate::{Error, Result};
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Options of [Profile::directives] which reference a file by their only argument.
+pub(super) const FILE_DIRECTIVES: [&str; 3] = ["extra-certs", "crl-verify", "http-proxy-user-pass"];
+
+/// A file referenced by a profile which could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileError {
+    /// Name of the option referencing the file.
+    pub directive: String,
+    /// Path of the file, resolved against the directory of the profile.
+    pub path: PathBuf,
+    /// Kind of the I/O error.
+    pub kind: io::ErrorKind,
+    /// Description of the I/O error.
+    pub message: String,
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            self.directive,
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+impl Profile {
+    /// Read and parse a profile from a file, and embed all files it references as inline blocks.
+    ///
+    /// See [Profile::inline_files].
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut profile = Self::parse(&fs::read_to_string(path)?)?;
+        profile.inline_files(path.parent().unwrap_or_else(|| Path::new("")))?;
+
+        Ok(profile)
+    }
+
+    /// Embed all files referenced by the profile as inline blocks.
+    ///
+    /// This covers `ca`, `cert`, `key`, `tls-auth`, `tls-crypt`, `tls-crypt-v2`, `auth-user-pass`, `extra-certs`, `crl-verify` and `http-proxy-user-pass`. Relative paths are resolved against `base`, usually the directory containing the profile.
+    ///
+    /// # Returns
+    ///
+    /// [Error::UnreadableFiles] listing every file which could not be read. The profile is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use openvpn3_rs::{profile::Profile, Error};
+    ///
+    /// let mut profile = Profile::parse("ca /nonexistent/ca.crt\nauth-user-pass\n").unwrap();
+    ///
+    /// match profile.inline_files("/") {
+    ///     Err(Error::UnreadableFiles(errors)) => assert_eq!(errors[0].directive, "ca"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn inline_files<P: AsRef<Path>>(&mut self, base: P) -> Result<()> {
+        let base = base.as_ref();
+        let mut inlined = self.clone();
+        let mut errors = Vec::new();
+
+        let mut read = |directive: &str, path: &Path| -> Option<String> {
+            let path = base.join(path);
+            match fs::read_to_string(&path) {
+                Ok(content) => Some(content),
+                Err(err) => {
+                    errors.push(FileError {
+                        directive: directive.to_string(),
+                        path,
+                        kind: err.kind(),
+                        message: err.to_string(),
+                    });
+                    None
+                }
+            }
+        };
+
+        for (name, material) in [
+            ("ca", &mut inlined.ca),
+            ("cert", &mut inlined.cert),
+            ("key", &mut inlined.key),
+            ("tls-auth", &mut inlined.tls_auth),
+            ("tls-crypt", &mut inlined.tls_crypt),
+            ("tls-crypt-v2", &mut inlined.tls_crypt_v2),
+        ] {
+            if let Some(Material::File(path)) = material {
+                if let Some(content) = read(name, path) {
+                    *material = Some(Material::Inline(content));
+                }
+            }
+        }
+
+        if let Some(AuthUserPass::File(path)) = &inlined.auth_user_pass {
+            if let Some(content) = read("auth-user-pass", path) {
+                inlined.auth_user_pass = Some(AuthUserPass::Inline(content));
+            }
+        }
+
+        for directive in &mut inlined.directives {
+            if directive.inline.is_none()
+                && directive.args.len() == 1
+                && FILE_DIRECTIVES.contains(&directive.name.as_str())
+            {
+                if let Some(content) = read(&directive.name, Path::new(&directive.args[0])) {
+                    *directive = Directive::inline(&directive.name, &content);
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::UnreadableFiles(errors));
+        }
+
+        *self = inlined;
+        Ok(())
+    }
+}
@@ -0,0 +1,741 @@
+//! Parses OpenVPN configuration profiles into a typed model, which can be inspected and edited before it is imported.
+//!
+//! # Examples
+//!
+//! ```
+//! use openvpn3_rs::profile::{Material, Profile, Proto, Remote};
+//!
+//! let mut profile = Profile::parse(
+//!     "client\n\
+//!      dev tun\n\
+//!      remote vpn.example.com 1194 udp\n\
+//!      <ca>\n\
+//!      -----BEGIN CERTIFICATE-----\n\
+//!      </ca>\n",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(profile.remotes[0].host, "vpn.example.com");
+//! assert!(matches!(profile.ca, Some(Material::Inline(_))));
+//!
+//! profile.remotes.push(Remote::new("backup.example.com").port(443).proto(Proto::Tcp));
+//! assert_eq!(Profile::parse(&profile.to_string()).unwrap(), profile);
+//! ```
+
+mod inline;
+mod parser;
+mod validate;
+
+pub use inline::FileError;
+pub use parser::{parse_directives, Directive};
+pub use validate::{validate, Diagnostic, Severity, ValidationReport};
+
+use self::parser::invalid;
+
+use crate::{Error, Result};
+
+use std::{fmt, path::PathBuf, str::FromStr};
+
+/// Placeholder argument of options whose content follows in an inline block, e.g. `tls-auth [inline] 1`.
+const INLINE: &str = "[inline]";
+
+/// Transport protocol, as used by the `proto` and `remote` options.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Proto {
+    Udp,
+    Udp4,
+    Udp6,
+    Tcp,
+    Tcp4,
+    Tcp6,
+    TcpClient,
+    Tcp4Client,
+    Tcp6Client,
+}
+
+impl Proto {
+    /// Parse a protocol name, e.g. `udp` or `tcp-client`.
+    pub fn parse(proto: &str) -> Option<Self> {
+        match proto.to_ascii_lowercase().as_str() {
+            "udp" => Some(Self::Udp),
+            "udp4" => Some(Self::Udp4),
+            "udp6" => Some(Self::Udp6),
+            "tcp" => Some(Self::Tcp),
+            "tcp4" => Some(Self::Tcp4),
+            "tcp6" => Some(Self::Tcp6),
+            "tcp-client" => Some(Self::TcpClient),
+            "tcp4-client" => Some(Self::Tcp4Client),
+            "tcp6-client" => Some(Self::Tcp6Client),
+            _ => None,
+        }
+    }
+
+    /// Check whether this is a TCP protocol.
+    pub fn is_tcp(&self) -> bool {
+        !matches!(self, Self::Udp | Self::Udp4 | Self::Udp6)
+    }
+}
+
+impl fmt::Display for Proto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Udp => write!(f, "udp"),
+            Self::Udp4 => write!(f, "udp4"),
+            Self::Udp6 => write!(f, "udp6"),
+            Self::Tcp => write!(f, "tcp"),
+            Self::Tcp4 => write!(f, "tcp4"),
+            Self::Tcp6 => write!(f, "tcp6"),
+            Self::TcpClient => write!(f, "tcp-client"),
+            Self::Tcp4Client => write!(f, "tcp4-client"),
+            Self::Tcp6Client => write!(f, "tcp6-client"),
+        }
+    }
+}
+
+/// A VPN server to connect to, from a `remote` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Remote {
+    /// Host name or IP address.
+    pub host: String,
+    /// Port, if it differs from the `port` option.
+    pub port: Option<u16>,
+    /// Protocol, if it differs from the `proto` option.
+    pub proto: Option<Proto>,
+}
+
+impl Remote {
+    /// Create a new [Remote] using the default port and protocol.
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: None,
+            proto: None,
+        }
+    }
+
+    /// Set the port.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the protocol. A protocol is only written if a port is set as well.
+    pub fn proto(mut self, proto: Proto) -> Self {
+        self.proto = Some(proto);
+        self
+    }
+}
+
+/// A route pushed into the routing table once connected, from a `route` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    /// Destination network or host.
+    pub network: String,
+    /// Netmask of the destination network.
+    pub netmask: Option<String>,
+    /// Gateway, `vpn_gateway` by default.
+    pub gateway: Option<String>,
+    /// Route metric.
+    pub metric: Option<u32>,
+}
+
+impl Route {
+    /// Create a new [Route] to a network.
+    pub fn new(network: &str, netmask: &str) -> Self {
+        Self {
+            network: network.to_string(),
+            netmask: Some(netmask.to_string()),
+            gateway: None,
+            metric: None,
+        }
+    }
+}
+
+/// A certificate or key, either referenced by path or embedded inline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Material {
+    /// Path to a file, relative to the profile.
+    File(PathBuf),
+    /// Content of an inline block.
+    Inline(String),
+}
+
+/// Source of the username and password, from an `auth-user-pass` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthUserPass {
+    /// Ask the user for credentials.
+    Prompt,
+    /// Read the credentials from a file.
+    File(PathBuf),
+    /// Credentials embedded in an inline block.
+    Inline(String),
+}
+
+/// Kind of an option at a position of a parsed profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Slot {
+    /// An option with a dedicated field of [Profile], by its name.
+    Field(String),
+    /// One of [Profile::directives].
+    Directive,
+}
+
+/// Order of the options of a parsed profile.
+///
+/// Only affects the order in which options are written, so all layouts compare equal.
+#[derive(Clone, Debug, Default)]
+struct Layout(Vec<Slot>);
+
+impl PartialEq for Layout {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Layout {}
+
+/// OpenVPN Configuration Profile
+///
+/// Options without a dedicated field are kept in [Profile::directives], in the order they appear in. Comments are not preserved.
+///
+/// [fmt::Display] writes the options of a parsed profile in the order they were parsed in. Options added afterwards are written after the last option of the same kind, or at the end if there is none. The options of a profile created with [Profile::new] are written in a canonical order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// `client`
+    pub client: bool,
+    /// `dev`, e.g. `tun`.
+    pub dev: Option<String>,
+    /// `proto`
+    pub proto: Option<Proto>,
+    /// `remote`, in order of preference.
+    pub remotes: Vec<Remote>,
+    /// `remote-random`
+    pub remote_random: bool,
+    /// `cipher`
+    pub cipher: Option<String>,
+    /// `data-ciphers`
+    pub data_ciphers: Vec<String>,
+    /// `data-ciphers-fallback`
+    pub data_ciphers_fallback: Option<String>,
+    /// `auth`, the HMAC digest algorithm.
+    pub auth: Option<String>,
+    /// `auth-user-pass`
+    pub auth_user_pass: Option<AuthUserPass>,
+    /// `auth-nocache`
+    pub auth_nocache: bool,
+    /// `route`
+    pub routes: Vec<Route>,
+    /// `ca`
+    pub ca: Option<Material>,
+    /// `cert`
+    pub cert: Option<Material>,
+    /// `key`
+    pub key: Option<Material>,
+    /// `tls-auth`
+    pub tls_auth: Option<Material>,
+    /// `tls-crypt`
+    pub tls_crypt: Option<Material>,
+    /// `tls-crypt-v2`
+    pub tls_crypt_v2: Option<Material>,
+    /// `key-direction`, or the direction argument of `tls-auth`.
+    pub key_direction: Option<u8>,
+    /// All other options.
+    pub directives: Vec<Directive>,
+    layout: Layout,
+}
+
+impl Profile {
+    /// Create an empty [Profile].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a configuration profile.
+    ///
+    /// If an option which only takes effect once is given more than once, the last one wins.
+    ///
+    /// # Returns
+    ///
+    /// [Error::InvalidProfile] if the syntax is invalid or an option has invalid arguments.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut profile = Self::new();
+
+        for (line, directive) in parse_directives(text)? {
+            let name = match directive.name.as_str() {
+                "ncp-ciphers" => String::from("data-ciphers"),
+                name => name.to_string(),
+            };
+            let count = profile.directives.len();
+
+            profile.apply(line, directive)?;
+
+            profile.layout.0.push(if profile.directives.len() > count {
+                Slot::Directive
+            } else {
+                Slot::Field(name)
+            });
+        }
+
+        Ok(profile)
+    }
+
+    /// Get the first of [Profile::directives] with a name.
+    pub fn directive(&self, name: &str) -> Option<&Directive> {
+        self.directives
+            .iter()
+            .find(|directive| directive.name == name)
+    }
+
+    /// Convert the profile back to its options, in the order they are written in.
+    pub fn to_directives(&self) -> Vec<Directive> {
+        let mut pending: Vec<Option<(Slot, Directive)>> =
+            self.canonical().into_iter().map(Some).collect();
+        let mut directives = Vec::new();
+        let layout = &self.layout.0;
+
+        for (index, slot) in layout.iter().enumerate() {
+            // Options without a position follow the last option of the same kind
+            let last = !layout[index + 1..].contains(slot);
+
+            for entry in pending.iter_mut() {
+                if entry.as_ref().is_some_and(|(kind, _)| kind == slot) {
+                    directives.extend(entry.take().map(|(_, directive)| directive));
+                    if !last {
+                        break;
+                    }
+                }
+            }
+        }
+
+        directives.extend(
+            pending
+                .into_iter()
+                .flatten()
+                .map(|(_, directive)| directive),
+        );
+        directives
+    }
+
+    /// The options of the profile in canonical order, each with its kind.
+    fn canonical(&self) -> Vec<(Slot, Directive)> {
+        let mut directives = Vec::new();
+        let no_args: [&str; 0] = [];
+
+        if self.client {
+            directives.push(Directive::new("client", no_args));
+        }
+        if let Some(dev) = &self.dev {
+            directives.push(Directive::new("dev", [dev.as_str()]));
+        }
+        if let Some(proto) = self.proto {
+            directives.push(Directive::new("proto", [proto.to_string()]));
+        }
+        for remote in &self.remotes {
+            let mut args = vec![remote.host.clone()];
+            if let Some(port) = remote.port {
+                args.push(port.to_string());
+                args.extend(remote.proto.map(|proto| proto.to_string()));
+            }
+            directives.push(Directive::new("remote", args));
+        }
+        if self.remote_random {
+            directives.push(Directive::new("remote-random", no_args));
+        }
+        if let Some(cipher) = &self.cipher {
+            directives.push(Directive::new("cipher", [cipher.as_str()]));
+        }
+        if !self.data_ciphers.is_empty() {
+            directives.push(Directive::new(
+                "data-ciphers",
+                [self.data_ciphers.join(":")],
+            ));
+        }
+        if let Some(cipher) = &self.data_ciphers_fallback {
+            directives.push(Directive::new("data-ciphers-fallback", [cipher.as_str()]));
+        }
+        if let Some(auth) = &self.auth {
+            directives.push(Directive::new("auth", [auth.as_str()]));
+        }
+        match &self.auth_user_pass {
+            Some(AuthUserPass::Prompt) => {
+                directives.push(Directive::new("auth-user-pass", no_args))
+            }
+            Some(AuthUserPass::File(path)) => directives.push(Directive::new(
+                "auth-user-pass",
+                [path.to_string_lossy().into_owned()],
+            )),
+            Some(AuthUserPass::Inline(content)) => {
+                directives.push(Directive::inline("auth-user-pass", content))
+            }
+            None => {}
+        }
+        if self.auth_nocache {
+            directives.push(Directive::new("auth-nocache", no_args));
+        }
+        for route in &self.routes {
+            let mut args = vec![route.network.clone()];
+            if route.gateway.is_some() || route.metric.is_some() {
+                args.push(
+                    route
+                        .netmask
+                        .clone()
+                        .unwrap_or_else(|| String::from("255.255.255.255")),
+                );
+                args.push(
+                    route
+                        .gateway
+                        .clone()
+                        .unwrap_or_else(|| String::from("default")),
+                );
+            } else {
+                args.extend(route.netmask.clone());
+            }
+            args.extend(route.metric.map(|metric| metric.to_string()));
+            directives.push(Directive::new("route", args));
+        }
+
+        let others = directives.len()..directives.len() + self.directives.len();
+        directives.extend(self.directives.iter().cloned());
+
+        if let Some(direction) = self.key_direction {
+            directives.push(Directive::new("key-direction", [direction.to_string()]));
+        }
+        for (name, material) in [
+            ("ca", &self.ca),
+            ("cert", &self.cert),
+            ("key", &self.key),
+            ("tls-auth", &self.tls_auth),
+            ("tls-crypt", &self.tls_crypt),
+            ("tls-crypt-v2", &self.tls_crypt_v2),
+        ] {
+            match material {
+                Some(Material::File(path)) => {
+                    directives.push(Directive::new(name, [path.to_string_lossy().into_owned()]))
+                }
+                Some(Material::Inline(content)) => {
+                    directives.push(Directive::inline(name, content))
+                }
+                None => {}
+            }
+        }
+
+        directives
+            .into_iter()
+            .enumerate()
+            .map(|(index, directive)| {
+                if others.contains(&index) {
+                    (Slot::Directive, directive)
+                } else {
+                    (Slot::Field(directive.name.clone()), directive)
+                }
+            })
+            .collect()
+    }
+
+    fn apply(&mut self, line: usize, directive: Directive) -> Result<()> {
+        let Directive { name, args, inline } = directive;
+
+        if let Some(content) = inline {
+            match name.as_str() {
+                "auth-user-pass" => self.auth_user_pass = Some(AuthUserPass::Inline(content)),
+                _ => match self.material_mut(&name) {
+                    Some(material) => *material = Some(Material::Inline(content)),
+                    None => self.directives.push(Directive {
+                        name,
+                        args,
+                        inline: Some(content),
+                    }),
+                },
+            }
+            return Ok(());
+        }
+
+        let arity = |min: usize, max: usize| {
+            if args.len() < min || args.len() > max {
+                Err(invalid(
+                    line,
+                    format!("wrong number of arguments for {}", name),
+                ))
+            } else {
+                Ok(())
+            }
+        };
+
+        match name.as_str() {
+            "client" => {
+                arity(0, 0)?;
+                self.client = true;
+            }
+            "dev" => {
+                arity(1, 1)?;
+                self.dev = Some(args[0].clone());
+            }
+            "proto" => {
+                arity(1, 1)?;
+                self.proto = Some(parse_proto(line, &args[0])?);
+            }
+            "remote" => {
+                arity(1, 3)?;
+                self.remotes.push(Remote {
+                    host: args[0].clone(),
+                    port: args
+                        .get(1)
+                        .map(|port| {
+                            port.parse()
+                                .map_err(|_| invalid(line, format!("invalid port {}", port)))
+                        })
+                        .transpose()?,
+                    proto: args
+                        .get(2)
+                        .map(|proto| parse_proto(line, proto))
+                        .transpose()?,
+                });
+            }
+            "remote-random" => {
+                arity(0, 0)?;
+                self.remote_random = true;
+            }
+            "cipher" => {
+                arity(1, 1)?;
+                self.cipher = Some(args[0].clone());
+            }
+            "data-ciphers" | "ncp-ciphers" => {
+                arity(1, 1)?;
+                self.data_ciphers = args[0].split(':').map(String::from).collect();
+            }
+            "data-ciphers-fallback" => {
+                arity(1, 1)?;
+                self.data_ciphers_fallback = Some(args[0].clone());
+            }
+            "auth" => {
+                arity(1, 1)?;
+                self.auth = Some(args[0].clone());
+            }
+            "auth-user-pass" => {
+                arity(0, 1)?;
+                match args.first() {
+                    Some(path) if path == INLINE => {}
+                    Some(path) => {
+                        self.auth_user_pass = Some(AuthUserPass::File(PathBuf::from(path)))
+                    }
+                    None => self.auth_user_pass = Some(AuthUserPass::Prompt),
+                }
+            }
+            "auth-nocache" => {
+                arity(0, 0)?;
+                self.auth_nocache = true;
+            }
+            "route" => {
+                arity(1, 4)?;
+                self.routes.push(Route {
+                    network: args[0].clone(),
+                    netmask: args.get(1).cloned(),
+                    gateway: args.get(2).filter(|gateway| *gateway != "default").cloned(),
+                    metric: args
+                        .get(3)
+                        .filter(|metric| *metric != "default")
+                        .map(|metric| {
+                            metric
+                                .parse()
+                                .map_err(|_| invalid(line, format!("invalid metric {}", metric)))
+                        })
+                        .transpose()?,
+                });
+            }
+            "key-direction" => {
+                arity(1, 1)?;
+                self.key_direction = Some(parse_direction(line, &args[0])?);
+            }
+            "tls-auth" => {
+                arity(1, 2)?;
+                if let Some(direction) = args.get(1) {
+                    self.key_direction = Some(parse_direction(line, direction)?);
+                }
+                if args[0] != INLINE {
+                    self.tls_auth = Some(Material::File(PathBuf::from(&args[0])));
+                }
+            }
+            _ => match self.material_mut(&name) {
+                Some(material) => {
+                    arity(1, 1)?;
+                    if args[0] != INLINE {
+                        *material = Some(Material::File(PathBuf::from(&args[0])));
+                    }
+                }
+                None => self.directives.push(Directive {
+                    name,
+                    args,
+                    inline: None,
+                }),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn material_mut(&mut self, name: &str) -> Option<&mut Option<Material>> {
+        match name {
+            "ca" => Some(&mut self.ca),
+            "cert" => Some(&mut self.cert),
+            "key" => Some(&mut self.key),
+            "tls-auth" => Some(&mut self.tls_auth),
+            "tls-crypt" => Some(&mut self.tls_crypt),
+            "tls-crypt-v2" => Some(&mut self.tls_crypt_v2),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for directive in self.to_directives() {
+            writeln!(f, "{}", directive)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_proto(line: usize, proto: &str) -> Result<Proto> {
+    Proto::parse(proto).ok_or_else(|| invalid(line, format!("unknown protocol {}", proto)))
+}
+
+fn parse_direction(line: usize, direction: &str) -> Result<u8> {
+    match direction {
+        "0" => Ok(0),
+        "1" => Ok(1),
+        _ => Err(invalid(
+            line,
+            format!("invalid key direction {}", direction),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let profile = Profile::parse(
+            "client\n\
+             dev tun\n\
+             ; a comment\n\
+             remote vpn.example.com 1194 udp # trailing comment\n\
+             route 10.0.0.0 255.0.0.0\n\
+             static-challenge \"Enter PIN\" 1\n\
+             tls-auth ta.key 1\n\
+             <ca>\n\
+             -----BEGIN CERTIFICATE-----\n\
+             </ca>\n",
+        )
+        .unwrap();
+
+        assert!(profile.client);
+        assert_eq!(profile.dev.as_deref(), Some("tun"));
+        assert_eq!(
+            profile.remotes,
+            vec![Remote::new("vpn.example.com").port(1194).proto(Proto::Udp)]
+        );
+        assert_eq!(profile.routes, vec![Route::new("10.0.0.0", "255.0.0.0")]);
+        assert_eq!(profile.key_direction, Some(1));
+        assert_eq!(
+            profile.tls_auth,
+            Some(Material::File(PathBuf::from("ta.key")))
+        );
+        assert_eq!(
+            profile.ca,
+            Some(Material::Inline(String::from(
+                "-----BEGIN CERTIFICATE-----\n"
+            )))
+        );
+        assert_eq!(
+            profile.directive("static-challenge").unwrap().args,
+            vec!["Enter PIN", "1"]
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(
+            Profile::parse("client\nremote vpn.example.com port\n"),
+            Err(Error::InvalidProfile {
+                line: 2,
+                message: String::from("invalid port port"),
+            })
+        );
+        assert_eq!(
+            Profile::parse("dev\n"),
+            Err(Error::InvalidProfile {
+                line: 1,
+                message: String::from("wrong number of arguments for dev"),
+            })
+        );
+        assert!(Profile::parse("proto sctp\n").is_err());
+        assert!(Profile::parse("key-direction 2\n").is_err());
+    }
+
+    #[test]
+    fn keep_order() {
+        let text = "client\n\
+                    remote a.example.com 1194\n\
+                    <connection>\n\
+                    remote b.example.com 443 tcp\n\
+                    </connection>\n\
+                    remote c.example.com 1194\n\
+                    verb 3\n\
+                    route 10.0.0.0 255.0.0.0\n\
+                    setenv FOO bar\n\
+                    dev tun\n";
+        let mut profile = Profile::parse(text).unwrap();
+        assert_eq!(profile.to_string(), text);
+
+        // added options follow the last option of the same kind
+        profile.remotes.push(Remote::new("d.example.com"));
+        profile.cipher = Some(String::from("AES-256-GCM"));
+        assert_eq!(
+            profile.to_string(),
+            "client\n\
+             remote a.example.com 1194\n\
+             <connection>\n\
+             remote b.example.com 443 tcp\n\
+             </connection>\n\
+             remote c.example.com 1194\n\
+             remote d.example.com\n\
+             verb 3\n\
+             route 10.0.0.0 255.0.0.0\n\
+             setenv FOO bar\n\
+             dev tun\n\
+             cipher AES-256-GCM\n"
+        );
+        assert_eq!(Profile::parse(&profile.to_string()).unwrap(), profile);
+
+        // removed options are left out
+        profile.remotes.remove(0);
+        assert!(!profile.to_string().contains("a.example.com"));
+    }
+
+    #[test]
+    fn canonical_order() {
+        let mut profile = Profile::new();
+        profile.directives.push(Directive::new("verb", ["3"]));
+        profile.ca = Some(Material::Inline(String::from("CA\n")));
+        profile
+            .remotes
+            .push(Remote::new("vpn.example.com").port(1194));
+        profile.client = true;
+
+        assert_eq!(
+            profile.to_string(),
+            "client\nremote vpn.example.com 1194\nverb 3\n<ca>\nCA\n</ca>\n"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Tokenizer for the OpenVPN configuration file syntax.
+
+use crate::{Error, Result};
+
+use std::fmt;
+
+/// A single option of a configuration profile, e.g. `remote vpn.example.com 1194` or an inline `<ca>` block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Directive {
+    /// Name of the option, without a leading `--`.
+    pub name: String,
+    /// Arguments of the option, with quoting removed.
+    pub args: Vec<String>,
+    /// Content of an inline block, including the trailing newline of its last line.
+    pub inline: Option<String>,
+}
+
+impl Directive {
+    /// Create a directive with arguments.
+    pub fn new<I, S>(name: &str, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.to_string(),
+            args: args.into_iter().map(Into::into).collect(),
+            inline: None,
+        }
+    }
+
+    /// Create an inline block.
+    pub fn inline(name: &str, content: &str) -> Self {
+        let mut content = content.to_string();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+
+        Self {
+            name: name.to_string(),
+            args: Vec::new(),
+            inline: Some(content),
+        }
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(content) = &self.inline {
+            return write!(f, "<{}>\n{}</{}>", self.name, content, self.name);
+        }
+
+        write!(f, "{}", self.name)?;
+        for arg in &self.args {
+            write!(f, " {}", quote(arg))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Split a configuration profile into its directives.
+///
+/// Comments starting with `#` or `;` and empty lines are skipped. Arguments may be quoted with `"` or `'`, and a `\` escapes the next character outside of single quotes.
+///
+/// # Returns
+///
+/// Each directive together with the number of the line it starts on, counting from 1.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::profile::parse_directives;
+///
+/// let directives = parse_directives("# comment\nremote \"my server\" 1194\n<ca>\nPEM\n</ca>\n").unwrap();
+///
+/// assert_eq!(directives[0].0, 2);
+/// assert_eq!(directives[0].1.args, vec!["my server", "1194"]);
+/// assert_eq!(directives[1].1.inline.as_deref(), Some("PEM\n"));
+/// ```
+pub fn parse_directives(text: &str) -> Result<Vec<(usize, Directive)>> {
+    let mut directives = Vec::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some(name) = trimmed.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+            if name.starts_with('/') {
+                return Err(invalid(number, format!("unexpected {}", trimmed)));
+            }
+
+            let closing = format!("</{}>", name);
+            let mut content = String::new();
+            loop {
+                match lines.next() {
+                    Some((_, line)) if line.trim() == closing => break,
+                    Some((_, line)) => {
+                        content.push_str(line);
+                        content.push('\n');
+                    }
+                    None => return Err(invalid(number, format!("missing {}", closing))),
+                }
+            }
+
+            directives.push((
+                number,
+                Directive {
+                    name: name.to_string(),
+                    args: Vec::new(),
+                    inline: Some(content),
+                },
+            ));
+            continue;
+        }
+
+        let mut tokens = tokenize(line, number)?.into_iter();
+        if let Some(name) = tokens.next() {
+            directives.push((
+                number,
+                Directive {
+                    name: name.strip_prefix("--").unwrap_or(&name).to_string(),
+                    args: tokens.collect(),
+                    inline: None,
+                },
+            ));
+        }
+    }
+
+    Ok(directives)
+}
+
+fn tokenize(line: &str, number: usize) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if matches!(chars.peek(), None | Some('#') | Some(';')) {
+            return Ok(tokens);
+        }
+
+        let mut token = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                '"' => loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => token.push(escaped),
+                            None => return Err(invalid(number, "unterminated quote")),
+                        },
+                        Some(c) => token.push(c),
+                        None => return Err(invalid(number, "unterminated quote")),
+                    }
+                },
+                '\'' => loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => token.push(c),
+                        None => return Err(invalid(number, "unterminated quote")),
+                    }
+                },
+                '\\' => token.extend(chars.next()),
+                c => token.push(c),
+            }
+        }
+        tokens.push(token);
+    }
+}
+
+fn quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | '#' | ';'))
+    {
+        return arg.to_string();
+    }
+
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub(super) fn invalid<M: Into<String>>(line: usize, message: M) -> Error {
+    Error::InvalidProfile {
+        line,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<(usize, Directive)> {
+        parse_directives(text).unwrap()
+    }
+
+    #[test]
+    fn comments_and_quotes() {
+        let directives = parse(
+            "# comment\n\
+             \n\
+             ; another comment\n\
+             --remote 'my server' 1194 # trailing\n\
+             static-challenge \"Enter \\\"PIN\\\"\" 1\n\
+             setenv NAME a\\ b;c\n",
+        );
+
+        assert_eq!(
+            directives[0],
+            (4, Directive::new("remote", ["my server", "1194"]))
+        );
+        assert_eq!(
+            directives[1],
+            (
+                5,
+                Directive::new("static-challenge", ["Enter \"PIN\"", "1"])
+            )
+        );
+        assert_eq!(
+            directives[2],
+            (6, Directive::new("setenv", ["NAME", "a b;c"]))
+        );
+    }
+
+    #[test]
+    fn inline_blocks() {
+        let directives = parse("ca [inline]\n<ca>\nPEM\n  </ca>\nverb 3\n");
+
+        assert_eq!(directives[0], (1, Directive::new("ca", ["[inline]"])));
+        assert_eq!(directives[1], (2, Directive::inline("ca", "PEM")));
+        assert_eq!(directives[2], (5, Directive::new("verb", ["3"])));
+    }
+
+    #[test]
+    fn invalid_syntax() {
+        assert_eq!(
+            parse_directives("<ca>\nPEM\n"),
+            Err(invalid(1, "missing </ca>"))
+        );
+        assert_eq!(
+            parse_directives("client\n</ca>\n"),
+            Err(invalid(2, "unexpected </ca>"))
+        );
+        assert_eq!(
+            parse_directives("remote \"vpn.example.com\n"),
+            Err(invalid(1, "unterminated quote"))
+        );
+    }
+
+    #[test]
+    fn display_quotes() {
+        let directive = Directive::new("static-challenge", ["Enter \"PIN\"", "1"]);
+        assert_eq!(
+            directive.to_string(),
+            "static-challenge \"Enter \\\"PIN\\\"\" 1"
+        );
+        assert_eq!(
+            Directive::new("setenv", ["A", ""]).to_string(),
+            "setenv A \"\""
+        );
+        assert_eq!(parse(&directive.to_string()), vec![(1, directive)]);
+    }
+}
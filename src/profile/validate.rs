@@ -0,0 +1,451 @@
+//! Checks a profile for problems which would otherwise only be reported by the OpenVPN 3 services after import.
+
+use super::{inline::FILE_DIRECTIVES, parse_directives, AuthUserPass, Profile, Proto, INLINE};
+
+use crate::Error;
+
+use std::{collections::HashMap, fmt};
+
+/// Options which OpenVPN 3 Core rejects.
+const UNSUPPORTED: [&str; 6] = [
+    "secret",
+    "server",
+    "tls-server",
+    "plugin",
+    "socks-proxy",
+    "client-config-dir",
+];
+
+/// Options which OpenVPN 3 Core silently ignores, mostly scripts and process management.
+const IGNORED: [&str; 15] = [
+    "up",
+    "down",
+    "route-up",
+    "ipchange",
+    "tls-verify",
+    "script-security",
+    "management",
+    "daemon",
+    "log",
+    "log-append",
+    "status",
+    "writepid",
+    "user",
+    "group",
+    "chroot",
+];
+
+/// Options which only take effect once, the last occurrence overriding earlier ones.
+const SINGULAR: [&str; 9] = [
+    "dev",
+    "proto",
+    "cipher",
+    "data-ciphers",
+    "data-ciphers-fallback",
+    "auth",
+    "ca",
+    "cert",
+    "key",
+];
+
+/// Ciphers and digests which are disabled in OpenVPN 3 unless legacy algorithms are enabled.
+const DEPRECATED_ALGORITHMS: [&str; 11] = [
+    "BF-CBC",
+    "CAST5-CBC",
+    "DES-CBC",
+    "DES-EDE-CBC",
+    "DES-EDE3-CBC",
+    "DESX-CBC",
+    "IDEA-CBC",
+    "RC2-CBC",
+    "RC2-40-CBC",
+    "RC2-64-CBC",
+    "MD5",
+];
+
+/// Severity of a [Diagnostic].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The profile works, but probably not as intended.
+    Warning,
+    /// The profile will be rejected, or the connection will fail.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in a profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Line the problem was found on, or `None` if it concerns the profile as a whole, e.g. a missing option.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Result of [validate].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// All problems found, ordered by line. Problems concerning the profile as a whole come last.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Check whether no errors were found. Warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Iterate over the diagnostics with [Severity::Error].
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Iterate over the diagnostics with [Severity::Warning].
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
+
+    fn push<M: Into<String>>(&mut self, severity: Severity, line: Option<usize>, message: M) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            line,
+            message: message.into(),
+        });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}", diagnostic)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a profile before it is imported.
+///
+/// Reports syntax errors, options OpenVPN 3 Core does not support, files which are not embedded inline, missing or conflicting options, deprecated ciphers, and `auth-user-pass` without credentials.
+///
+/// # Examples
+///
+/// ```
+/// use openvpn3_rs::profile::{validate, Severity};
+///
+/// let report = validate("client\nremote vpn.example.com\ncipher BF-CBC\nca ca.crt\n");
+///
+/// assert!(!report.is_valid());
+/// assert_eq!(report.diagnostics[0].line, Some(3));
+/// assert_eq!(report.diagnostics[0].severity, Severity::Warning);
+/// assert_eq!(report.diagnostics[1].line, Some(4));
+/// assert_eq!(report.diagnostics[1].severity, Severity::Error);
+/// ```
+pub fn validate(text: &str) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let (directives, profile) = match parse_directives(text)
+        .and_then(|directives| Profile::parse(text).map(|profile| (directives, profile)))
+    {
+        Ok(parsed) => parsed,
+        Err(Error::InvalidProfile { line, message }) => {
+            report.push(Severity::Error, Some(line), message);
+            return report;
+        }
+        Err(err) => {
+            report.push(Severity::Error, None, err.to_string());
+            return report;
+        }
+    };
+
+    let blocks: Vec<&str> = directives
+        .iter()
+        .filter(|(_, directive)| directive.inline.is_some())
+        .map(|(_, directive)| directive.name.as_str())
+        .collect();
+    let line_of = |name: &str| {
+        directives
+            .iter()
+            .rev()
+            .find(|(_, directive)| directive.name == name)
+            .map(|(line, _)| *line)
+    };
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    for (number, directive) in &directives {
+        let name = directive.name.as_str();
+        let line = Some(*number);
+
+        if directive.inline.is_some() {
+            if name == "auth-user-pass" && directive.inline.as_deref() == Some("") {
+                report.push(Severity::Error, line, "empty <auth-user-pass> block");
+            }
+            continue;
+        }
+
+        if UNSUPPORTED.contains(&name)
+            || (name == "mode" && directive.args.first().map(String::as_str) == Some("server"))
+        {
+            report.push(
+                Severity::Error,
+                line,
+                format!("{} is not supported by OpenVPN 3 Core", name),
+            );
+        } else if IGNORED.contains(&name) {
+            report.push(
+                Severity::Warning,
+                line,
+                format!("{} is ignored by OpenVPN 3 Core", name),
+            );
+        }
+
+        if SINGULAR.contains(&name) {
+            if let Some(previous) = seen.insert(name, *number) {
+                report.push(
+                    Severity::Warning,
+                    line,
+                    format!("{} overrides the one on line {}", name, previous),
+                );
+            }
+        }
+
+        let references_file = matches!(
+            name,
+            "ca" | "cert" | "key" | "tls-auth" | "tls-crypt" | "tls-crypt-v2" | "auth-user-pass"
+        ) || FILE_DIRECTIVES.contains(&name);
+        if references_file {
+            match directive.args.first() {
+                Some(arg) if arg != INLINE => report.push(
+                    Severity::Error,
+                    line,
+                    format!(
+                        "{} {} must be embedded inline, OpenVPN 3 cannot read files",
+                        name, arg
+                    ),
+                ),
+                Some(_) if !blocks.contains(&name) => report.push(
+                    Severity::Error,
+                    line,
+                    format!("missing inline block <{}>", name),
+                ),
+                _ => {}
+            }
+        }
+
+        if matches!(
+            name,
+            "cipher" | "data-ciphers" | "ncp-ciphers" | "data-ciphers-fallback" | "auth"
+        ) {
+            for algorithm in directive.args.iter().flat_map(|arg| arg.split(':')) {
+                if DEPRECATED_ALGORITHMS.contains(&algorithm.to_ascii_uppercase().as_str()) {
+                    report.push(
+                        Severity::Warning,
+                        line,
+                        format!(
+                            "{} is deprecated and disabled unless legacy algorithms are enabled",
+                            algorithm
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    let connection_remotes = directives
+        .iter()
+        .filter(|(_, directive)| directive.name == "connection")
+        .filter_map(|(_, directive)| directive.inline.as_deref())
+        .filter_map(|content| parse_directives(content).ok())
+        .any(|block| {
+            block
+                .iter()
+                .any(|(_, directive)| directive.name == "remote")
+        });
+    if profile.remotes.is_empty() && !connection_remotes {
+        report.push(Severity::Error, None, "no remote is configured");
+    }
+
+    if profile.ca.is_none() && profile.directive("peer-fingerprint").is_none() {
+        report.push(
+            Severity::Error,
+            None,
+            "no ca or peer-fingerprint to verify the server with",
+        );
+    }
+
+    if profile.cert.is_some() != profile.key.is_some() && profile.directive("pkcs12").is_none() {
+        let (present, missing) = if profile.cert.is_some() {
+            ("cert", "key")
+        } else {
+            ("key", "cert")
+        };
+        report.push(
+            Severity::Error,
+            line_of(present),
+            format!("{} is given without {}", present, missing),
+        );
+    }
+
+    let tls_keys: Vec<&str> = [
+        ("tls-auth", &profile.tls_auth),
+        ("tls-crypt", &profile.tls_crypt),
+        ("tls-crypt-v2", &profile.tls_crypt_v2),
+    ]
+    .into_iter()
+    .filter(|(_, material)| material.is_some())
+    .map(|(name, _)| name)
+    .collect();
+    if tls_keys.len() > 1 {
+        report.push(
+            Severity::Error,
+            tls_keys.iter().filter_map(|name| line_of(name)).max(),
+            format!("only one of {} can be used", tls_keys.join(", ")),
+        );
+    }
+
+    if profile.key_direction.is_some() && profile.tls_auth.is_none() {
+        if let Some(line) = line_of("key-direction") {
+            report.push(
+                Severity::Warning,
+                Some(line),
+                "key-direction has no effect without tls-auth",
+            );
+        }
+    }
+
+    if let Some(line) = line_of("http-proxy") {
+        let default = profile.proto.unwrap_or(Proto::Udp);
+        let udp = if profile.remotes.is_empty() {
+            !default.is_tcp()
+        } else {
+            profile
+                .remotes
+                .iter()
+                .any(|remote| !remote.proto.unwrap_or(default).is_tcp())
+        };
+        if udp {
+            report.push(
+                Severity::Error,
+                Some(line),
+                "http-proxy requires proto tcp-client",
+            );
+        }
+    }
+
+    match &profile.auth_user_pass {
+        Some(AuthUserPass::Prompt) => report.push(
+            Severity::Warning,
+            line_of("auth-user-pass"),
+            "auth-user-pass has no credentials, they must be provided when connecting",
+        ),
+        Some(AuthUserPass::Inline(content)) if content.lines().count() == 1 => report.push(
+            Severity::Warning,
+            line_of("auth-user-pass"),
+            "<auth-user-pass> has no password, it must be provided when connecting",
+        ),
+        _ => {}
+    }
+
+    report
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.line.unwrap_or(usize::MAX));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics() {
+        let report = validate(
+            "client\n\
+             dev tun\n\
+             proto udp\n\
+             remote vpn.example.com 1194\n\
+             http-proxy proxy.example.com 8080\n\
+             script-security 2\n\
+             up /etc/openvpn/up.sh\n\
+             secret static.key\n\
+             data-ciphers AES-256-GCM:BF-CBC\n\
+             dev tap\n\
+             auth-user-pass\n\
+             cert [inline]\n\
+             tls-auth [inline] 1\n\
+             tls-crypt [inline]\n\
+             <tls-auth>\n\
+             TA\n\
+             </tls-auth>\n\
+             <tls-crypt>\n\
+             TC\n\
+             </tls-crypt>\n",
+        );
+        let diagnostics: Vec<(Option<usize>, Severity)> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.severity))
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                (Some(5), Severity::Error),
+                (Some(6), Severity::Warning),
+                (Some(7), Severity::Warning),
+                (Some(8), Severity::Error),
+                (Some(9), Severity::Warning),
+                (Some(10), Severity::Warning),
+                (Some(11), Severity::Warning),
+                (Some(12), Severity::Error),
+                (Some(18), Severity::Error),
+                (None, Severity::Error),
+            ],
+            "{}",
+            report
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn connection_blocks() {
+        let report = validate(
+            "client\n\
+             persist-tun\n\
+             <connection>\n\
+             remote vpn.example.com 1194\n\
+             </connection>\n\
+             <ca>\n\
+             CA\n\
+             </ca>\n",
+        );
+        assert!(report.diagnostics.is_empty(), "{}", report);
+
+        let report = validate("client\n<connection>\nproto tcp\n</connection>\n<ca>\nCA\n</ca>\n");
+        assert_eq!(
+            report.errors().next().unwrap().to_string(),
+            "error: no remote is configured"
+        );
+    }
+}
@@ -3,7 +3,7 @@
 //! This code was generated by `zbus-xmlgen` `3.1.0` from DBus introspection data.
 //! Source: `net.openvpn.v3.configuration.xml`.
 
-use crate::log::constants::{LogGroup, LogLevel};
+use crate::log::constants::{LogCategory, LogGroup};
 
 use super::configuration_node::{ConfigurationNodeProxy, ConfigurationNodeProxyBlocking};
 
@@ -78,7 +78,7 @@ trait Configuration {
     /// Whenever the configuration manager wants to log something, it issues a Log signal which carries a log group, log verbosity level and a string with the log message itself.
     /// See the separate [logging documentation](https://github.com/OpenVPN/openvpn3-linux/blob/master/docs/dbus/dbus-logging.md) for details on this signal.
     #[dbus_proxy(signal)]
-    fn log(&self, group: LogGroup, level: LogLevel, message: &str) -> fdo::Result<()>;
+    fn log(&self, group: LogGroup, category: LogCategory, message: &str) -> fdo::Result<()>;
 
     /// Version of the currently running service.
     #[dbus_proxy(property, name = "version")]
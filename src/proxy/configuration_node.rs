@@ -96,6 +96,7 @@ trait ConfigurationNode {
     /// If set to true, the VPN tunnel will make use of the kernel accelerated Data Channel Offload (DCO) feature (requires kernel support).
     #[dbus_proxy(property, name = "dco")]
     fn dco(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "dco")]
     fn set_dco(&self, value: bool) -> fdo::Result<()>;
 
     /// Unix Epoch timestamp of the import time.
@@ -111,11 +112,13 @@ trait ConfigurationNode {
     /// If set to true, only the owner and openvpn user can retrieve the configuration file. Other users granted access can only use this profile to start a new tunnel.
     #[dbus_proxy(property, name = "locked_down")]
     fn locked_down(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "locked_down")]
     fn set_locked_down(&self, value: bool) -> fdo::Result<()>;
 
     /// Contains the user friendly name of the configuration profile
     #[dbus_proxy(property, name = "name")]
     fn name(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "name")]
     fn set_name(&self, value: &str) -> fdo::Result<()>;
 
     /// Contains all the override settings enabled. This is stored as a key/value based dictionary, where value can be any arbitrary data type.
@@ -135,6 +138,7 @@ trait ConfigurationNode {
     /// If set to true, access control is disabled. But only owner may change this property, modify the ACL or delete the configuration.
     #[dbus_proxy(property, name = "public_access")]
     fn public_access(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "public_access")]
     fn set_public_access(&self, value: bool) -> fdo::Result<()>;
 
     /// If set to true, the configuration have been sealed and can no longer be modified.
@@ -148,6 +152,7 @@ trait ConfigurationNode {
     /// If set to true, another user granted access to this profile will transfer the VPN session ownership back to the profile owner at start up.
     #[dbus_proxy(property, name = "transfer_owner_session")]
     fn transfer_owner_session(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "transfer_owner_session")]
     fn set_transfer_owner_session(&self, value: bool) -> fdo::Result<()>;
 
     /// Number of times Fetch has been called.
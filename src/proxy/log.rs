@@ -84,12 +84,14 @@ trait Log {
     /// Should each Log event being processed carry a meta data line before with details about the D-Bus sender of the Log signal?
     #[dbus_proxy(property, name = "log_dbus_details")]
     fn log_dbus_details(&self) -> zbus::Result<bool>;
-    fn set_log_dbus_details(&self, value: bool) -> zbus::Result<()>;
+    #[dbus_proxy(property, name = "log_dbus_details")]
+    fn set_log_dbus_details(&self, value: bool) -> zbus::fdo::Result<()>;
 
     /// How verbose should the logging be.
     #[dbus_proxy(property, name = "log_level")]
     fn log_level(&self) -> zbus::Result<LogLevel>;
-    fn set_log_level(&self, value: LogLevel) -> zbus::Result<()>;
+    #[dbus_proxy(property, name = "log_level")]
+    fn set_log_level(&self, value: LogLevel) -> zbus::fdo::Result<()>;
 
     /// Indicates which logging method is in use.
     #[dbus_proxy(property, name = "log_method")]
@@ -98,7 +100,8 @@ trait Log {
     /// Configures if logged messages should be prefixed with the log senders LogTag hash value.
     #[dbus_proxy(property, name = "log_prefix_logtag")]
     fn log_prefix_logtag(&self) -> zbus::Result<bool>;
-    fn set_log_prefix_logtag(&self, value: bool) -> zbus::Result<()>;
+    #[dbus_proxy(property, name = "log_prefix_logtag")]
+    fn set_log_prefix_logtag(&self, value: bool) -> zbus::fdo::Result<()>;
 
     /// Number of attached subscriptions.
     #[dbus_proxy(property, name = "num_attached")]
@@ -107,7 +110,8 @@ trait Log {
     /// Should each log line be prefixed with a timestamp?
     #[dbus_proxy(property, name = "timestamp")]
     fn timestamp(&self) -> zbus::Result<bool>;
-    fn set_timestamp(&self, value: bool) -> zbus::Result<()>;
+    #[dbus_proxy(property, name = "timestamp")]
+    fn set_timestamp(&self, value: bool) -> zbus::fdo::Result<()>;
 
     /// Version information about the running service.
     #[dbus_proxy(property, name = "version")]
@@ -128,7 +132,8 @@ trait LogNode {
     /// Verbosity level for log events to this recipient
     #[dbus_proxy(property, name = "log_level")]
     fn log_level(&self) -> zbus::Result<LogLevel>;
-    fn set_log_level(&self, value: LogLevel) -> zbus::Result<()>;
+    #[dbus_proxy(property, name = "log_level")]
+    fn set_log_level(&self, value: LogLevel) -> zbus::fdo::Result<()>;
 
     /// D-Bus object path to the VPN session.
     #[dbus_proxy(property, name = "session_path")]
@@ -144,12 +149,12 @@ pub mod constants {
 
     use serde_repr::{Deserialize_repr, Serialize_repr};
     use static_assertions::assert_impl_all;
-    use zbus::zvariant::{OwnedValue, Type};
+    use zbus::zvariant::Type;
 
     /// Log Group
     ///
     /// Source: openvpn3-linux/src/log/log-helpers.hpp
-    #[repr(u8)]
+    #[repr(u32)]
     #[derive(Deserialize_repr, Serialize_repr, Copy, Clone, Type, Debug, PartialEq, Eq)]
     pub enum LogGroup {
         /// Default - should not be used in code, but is here to detect errors
@@ -193,10 +198,23 @@ pub mod constants {
 
     assert_impl_all!(LogGroup: Send, Sync, Unpin);
 
+    try_from_u32!(LogGroup {
+        UNDEFINED,
+        MASTERPROC,
+        CONFIGMGR,
+        SESSIONMGR,
+        BACKENDSTART,
+        LOGGER,
+        BACKENDPROC,
+        CLIENT,
+        NETCFG,
+        EXTSERVICE,
+    });
+
     /// Log Category
     ///
     /// Source: openvpn3-linux/src/log/log-helpers.hpp
-    #[repr(u8)]
+    #[repr(u32)]
     #[derive(Deserialize_repr, Serialize_repr, Copy, Clone, Type, Debug, PartialEq, Eq)]
     pub enum LogCategory {
         /// Undefined/not set
@@ -221,6 +239,18 @@ pub mod constants {
 
     assert_impl_all!(LogCategory: Send, Sync, Unpin);
 
+    try_from_u32!(LogCategory {
+        UNDEFINED,
+        DEBUG,
+        VERB2,
+        VERB1,
+        INFO,
+        WARN,
+        ERROR,
+        CRIT,
+        FATAL,
+    });
+
     impl fmt::Display for LogCategory {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -240,7 +270,7 @@ pub mod constants {
     /// Log Level Filter
     ///
     /// Source: openvpn3-linux/src/log/dbus-log.hpp
-    #[repr(u8)]
+    #[repr(u32)]
     #[derive(Deserialize_repr, Serialize_repr, Copy, Clone, Type, Debug, PartialEq, Eq)]
     pub enum LogLevel {
         /// Log level 0 - Only FATAL and Critical messages are logged
@@ -261,11 +291,19 @@ pub mod constants {
 
     assert_impl_all!(LogLevel: Send, Sync, Unpin);
 
-    impl TryFrom<OwnedValue> for LogLevel {
-        type Error = <u8 as TryFrom<OwnedValue>>::Error;
+    try_from_u32!(LogLevel {
+        FATAL,
+        ERROR,
+        WARNING,
+        INFO,
+        VERB1,
+        VERB2,
+        DEBUG,
+    });
 
-        fn try_from(v: OwnedValue) -> Result<Self, Self::Error> {
-            Ok(unsafe { std::mem::transmute(<u8>::try_from(v)?) })
+    impl From<LogLevel> for zbus::zvariant::Value<'_> {
+        fn from(level: LogLevel) -> Self {
+            Self::from(level as u32)
         }
     }
 
@@ -1,3 +1,39 @@
+/// Implement checked conversions from the `u32` representation of a `#[repr(u32)]` enum, as sent by the services.
+///
+/// Values without a matching variant, e.g. ones added by newer services, are rejected.
+macro_rules! try_from_u32 {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl TryFrom<u32> for $name {
+            type Error = zbus::zvariant::Error;
+
+            fn try_from(value: u32) -> std::result::Result<Self, Self::Error> {
+                // fails to compile if a variant is missing from the list
+                let _ = |v: Self| match v {
+                    $(Self::$variant => (),)+
+                };
+
+                $(
+                    if value == Self::$variant as u32 {
+                        return Ok(Self::$variant);
+                    }
+                )+
+                Err(zbus::zvariant::Error::Message(format!(
+                    concat!("invalid ", stringify!($name), " value {}"),
+                    value
+                )))
+            }
+        }
+
+        impl TryFrom<zbus::zvariant::OwnedValue> for $name {
+            type Error = zbus::zvariant::Error;
+
+            fn try_from(v: zbus::zvariant::OwnedValue) -> std::result::Result<Self, Self::Error> {
+                Self::try_from(<u32>::try_from(v)?)
+            }
+        }
+    };
+}
+
 pub mod configuration;
 pub mod configuration_node;
 pub mod log;
@@ -8,6 +44,7 @@ pub mod sessions_node;
 
 pub use configuration::{ConfigurationProxy, ConfigurationProxyBlocking};
 pub use configuration_node::{ConfigurationNodeProxy, ConfigurationNodeProxyBlocking};
+pub use log::{LogNodeProxy, LogNodeProxyBlocking, LogProxy, LogProxyBlocking};
 pub use netcfg::{NetCfgProxy, NetCfgProxyBlocking};
 pub use netcfg_node::{NetCfgNodeProxy, NetCfgNodeProxyBlocking};
 pub use sessions::{SessionsProxy, SessionsProxyBlocking};
@@ -5,7 +5,7 @@
 
 use self::constants::*;
 use super::sessions_node::{SessionsNodeProxy, SessionsNodeProxyBlocking};
-use crate::log::constants::{LogGroup, LogLevel};
+use crate::log::constants::{LogCategory, LogGroup};
 use zbus::dbus_proxy;
 
 /// Session Service
@@ -81,7 +81,7 @@ trait Sessions {
     /// Whenever the configuration manager want to log something, it issues a Log signal which carries a log group, log verbosity level and a string with the log message itself.
     /// See the separate [logging documentation](https://github.com/OpenVPN/openvpn3-linux/blob/master/docs/dbus/dbus-logging.md) for details on this signal.
     #[dbus_proxy(signal)]
-    fn log(&self, group: LogGroup, level: LogLevel, message: &str) -> zbus::Result<()>;
+    fn log(&self, group: LogGroup, category: LogCategory, message: &str) -> zbus::Result<()>;
 
     /// SessionManagerEvent signal
     ///
@@ -4,7 +4,7 @@
 //! Source: `net.openvpn.v3.sessions.node.xml`.
 
 use self::constants::*;
-use crate::log::constants::{LogGroup, LogLevel};
+use crate::log::constants::{LogCategory, LogGroup, LogLevel};
 use zbus::{dbus_proxy, fdo};
 
 /// Session Object
@@ -174,7 +174,7 @@ pub trait SessionsNode {
     ///
     /// See [LogArgs].
     #[dbus_proxy(signal, name = "Log")]
-    fn log(&self, group: LogGroup, level: LogLevel, message: &str) -> zbus::Result<()>;
+    fn log(&self, group: LogGroup, category: LogCategory, message: &str) -> zbus::Result<()>;
 
     /// StatusChange signal
     ///
@@ -218,6 +218,7 @@ pub trait SessionsNode {
     /// Kernel based Data Channel Offload flag. Must be modified before calling `Connect()` to override the current setting.
     #[dbus_proxy(property, name = "dco")]
     fn dco(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "dco")]
     fn set_dco(&self, value: bool) -> fdo::Result<()>;
 
     /// device_name property
@@ -315,13 +316,13 @@ pub mod result {
         pub message: String,
     }
 
-    impl TryFrom<(u8, u8, String)> for Log {
-        type Error = <u8 as TryFrom<OwnedValue>>::Error;
+    impl TryFrom<(u32, u32, String)> for Log {
+        type Error = <u32 as TryFrom<OwnedValue>>::Error;
 
-        fn try_from(v: (u8, u8, String)) -> std::result::Result<Self, Self::Error> {
+        fn try_from(v: (u32, u32, String)) -> std::result::Result<Self, Self::Error> {
             Ok(Self {
-                group: unsafe { std::mem::transmute(<u8>::try_from(v.0)?) },
-                category: unsafe { std::mem::transmute(<u8>::try_from(v.1)?) },
+                group: LogGroup::try_from(v.0)?,
+                category: LogCategory::try_from(v.1)?,
                 message: v.2,
             })
         }
@@ -331,7 +332,7 @@ pub mod result {
         type Error = zbus::Error;
 
         fn try_from(v: OwnedValue) -> std::result::Result<Self, Self::Error> {
-            let v: (u8, u8, String) = v.try_into()?;
+            let v: (u32, u32, String) = v.try_into()?;
             Ok(Log::try_from(v)?)
         }
     }
@@ -354,8 +355,8 @@ pub mod result {
 
         fn try_from(v: (u32, u32, String)) -> std::result::Result<Self, Self::Error> {
             Ok(Self {
-                code_major: unsafe { std::mem::transmute(<u32>::try_from(v.0)?) },
-                code_minor: unsafe { std::mem::transmute(<u32>::try_from(v.1)?) },
+                code_major: StatusMajor::try_from(v.0)?,
+                code_minor: StatusMinor::try_from(v.1)?,
                 status_message: v.2,
             })
         }
@@ -378,7 +379,7 @@ pub mod constants {
 
     use serde_repr::{Deserialize_repr, Serialize_repr};
     use static_assertions::assert_impl_all;
-    use zbus::zvariant::{OwnedValue, Type};
+    use zbus::zvariant::Type;
 
     /// Client Attention Type
     ///
@@ -476,14 +477,14 @@ pub mod constants {
 
     assert_impl_all!(StatusMajor: Send, Sync, Unpin);
 
-    impl TryFrom<OwnedValue> for StatusMajor {
-        type Error = <u32 as TryFrom<OwnedValue>>::Error;
-
-        fn try_from(v: OwnedValue) -> Result<Self, Self::Error> {
-            // safe because StatusMajor has repr u32
-            Ok(unsafe { std::mem::transmute(<u32>::try_from(v)?) })
-        }
-    }
+    try_from_u32!(StatusMajor {
+        UNSET,
+        CONFIG,
+        CONNECTION,
+        SESSION,
+        PKCS11,
+        PROCESS,
+    });
 
     impl fmt::Display for StatusMajor {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -573,14 +574,38 @@ pub mod constants {
 
     assert_impl_all!(StatusMinor: Send, Sync, Unpin);
 
-    impl TryFrom<OwnedValue> for StatusMinor {
-        type Error = <u32 as TryFrom<OwnedValue>>::Error;
-
-        fn try_from(v: OwnedValue) -> Result<Self, Self::Error> {
-            // safe because StatusMinor has repr u32
-            Ok(unsafe { std::mem::transmute(<u32>::try_from(v)?) })
-        }
-    }
+    try_from_u32!(StatusMinor {
+        Unset,
+        CfgError,
+        CfgOk,
+        CfgInlineMissing,
+        CfgRequireUser,
+        ConnInit,
+        ConnConnecting,
+        ConnConnected,
+        ConnDisconnecting,
+        ConnDisconnected,
+        ConnFailed,
+        ConnAuthFailed,
+        ConnReconnecting,
+        ConnPausing,
+        ConnPaused,
+        ConnResuming,
+        ConnDone,
+        SessNew,
+        SessBackendCompleted,
+        SessRemoved,
+        SessAuthUserpass,
+        SessAuthChallenge,
+        SessAuthUrl,
+        PKCS11Sign,
+        PKCS11Encrypt,
+        PKCS11Decrypt,
+        PKCS11Verify,
+        ProcStarted,
+        ProcStopped,
+        ProcKilled,
+    });
 
     impl fmt::Display for StatusMinor {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -619,3 +644,40 @@ pub mod constants {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        constants::{StatusMajor, StatusMinor},
+        result::{Log, Status},
+    };
+    use crate::log::constants::{LogCategory, LogGroup};
+
+    #[test]
+    fn decode_values() {
+        assert_eq!(
+            Status::try_from((2, 7, String::new())).unwrap(),
+            Status {
+                code_major: StatusMajor::CONNECTION,
+                code_minor: StatusMinor::ConnConnected,
+                status_message: String::new(),
+            }
+        );
+        assert_eq!(
+            Log::try_from((7, 8, String::from("fatal"))).unwrap(),
+            Log {
+                group: LogGroup::CLIENT,
+                category: LogCategory::FATAL,
+                message: String::from("fatal"),
+            }
+        );
+        assert_eq!(
+            StatusMinor::try_from(StatusMinor::ProcKilled as u32),
+            Ok(StatusMinor::ProcKilled)
+        );
+
+        assert!(Status::try_from((6, 0, String::new())).is_err());
+        assert!(Status::try_from((2, 31, String::new())).is_err());
+        assert!(Log::try_from((0, 9, String::new())).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Timers and task spawning, backed by tokio with the `tokio` feature and by async-io otherwise.
+
+use crate::{Error, Result};
+
+use futures_util::{
+    future::{self, Either},
+    pin_mut,
+};
+use std::{future::Future, time::Duration};
+
+// zbus fails with the same message while it is compiled, so this is only reached if zbus is built with a runtime
+// feature enabled by another crate.
+#[cfg(not(any(feature = "async-io", feature = "tokio")))]
+compile_error!(
+    "openvpn3-rs: either the `async-io` (default) or the `tokio` feature must be enabled"
+);
+
+/// Wait until a duration has elapsed.
+pub async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio")]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(not(feature = "tokio"))]
+    async_io::Timer::after(duration).await;
+}
+
+/// Await a future, failing with [Error::Timeout] if it does not complete in time.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output> {
+    let sleep = sleep(duration);
+    pin_mut!(future, sleep);
+
+    match future::select(future, sleep).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(Error::Timeout),
+    }
+}
+
+/// The runtime which drives futures run by [block_on], and the D-Bus connections created by them.
+#[cfg(feature = "tokio")]
+fn shared() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start tokio runtime")
+    })
+}
+
+/// Run a future in the background.
+#[cfg(feature = "testing")]
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    #[cfg(feature = "tokio")]
+    enter(|| tokio::spawn(future));
+
+    #[cfg(not(feature = "tokio"))]
+    std::thread::spawn(move || async_io::block_on(future));
+}
+
+/// Call `f` where tasks can be spawned, without blocking.
+///
+/// With the `tokio` feature, `f` is called within the shared runtime of [block_on] unless the current thread already is within a runtime. Dropping proxies and streams spawns a task to remove their match rules, so this is needed to drop them outside of a runtime.
+pub(crate) fn enter<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_err() {
+        let _guard = shared().enter();
+        return f();
+    }
+
+    f()
+}
+
+/// Run a blocking function on a thread pool, so it does not stall the tasks of the async runtime.
+#[cfg_attr(not(feature = "users"), allow(dead_code))]
+pub(crate) async fn unblock<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "tokio")]
+    return enter(|| tokio::task::spawn_blocking(f))
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+
+    #[cfg(not(feature = "tokio"))]
+    blocking::unblock(f).await
+}
+
+/// Block the current thread on a future.
+///
+/// With the `tokio` feature the future runs on a shared multi-threaded runtime, which keeps serving D-Bus connections created on it after this returns. It must then not be called from within an async runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    #[cfg(feature = "tokio")]
+    return shared().block_on(future);
+
+    #[cfg(not(feature = "tokio"))]
+    async_io::block_on(future)
+}
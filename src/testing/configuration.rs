@@ -0,0 +1,371 @@
+//! Mock of the `net.openvpn.v3.configuration` service.
+
+use super::{now, ServerState, ServiceError};
+
+use crate::log::constants::{LogCategory, LogGroup};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use zbus::{
+    dbus_interface,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
+    ObjectServer, SignalContext,
+};
+
+/// State of a mocked configuration profile.
+#[derive(Debug)]
+pub(crate) struct ConfigurationData {
+    pub(crate) path: OwnedObjectPath,
+    pub(crate) name: String,
+    pub(crate) config_str: String,
+    pub(crate) single_use: bool,
+    pub(crate) persistent: bool,
+    pub(crate) import_timestamp: u64,
+    pub(crate) last_used_timestamp: u64,
+    pub(crate) used_count: u32,
+    pub(crate) owner: u32,
+    pub(crate) acl: Vec<u32>,
+    pub(crate) public_access: bool,
+    pub(crate) locked_down: bool,
+    pub(crate) sealed: bool,
+    pub(crate) dco: bool,
+    pub(crate) transfer_owner_session: bool,
+    pub(crate) overrides: HashMap<String, OwnedValue>,
+}
+
+impl ConfigurationData {
+    pub(crate) fn new(
+        path: OwnedObjectPath,
+        name: &str,
+        config_str: &str,
+        single_use: bool,
+        persistent: bool,
+    ) -> Self {
+        Self {
+            path,
+            name: name.to_string(),
+            config_str: config_str.to_string(),
+            single_use,
+            persistent,
+            import_timestamp: now(),
+            last_used_timestamp: 0,
+            used_count: 0,
+            owner: 0,
+            acl: Vec::new(),
+            public_access: false,
+            locked_down: false,
+            sealed: false,
+            dco: false,
+            transfer_owner_session: false,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Directives of the profile as a JSON object, the last occurrence of a directive wins.
+    fn json(&self) -> serde_json::Value {
+        let mut options = serde_json::Map::new();
+
+        for line in self.config_str.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            let (directive, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            options.insert(
+                directive.to_string(),
+                serde_json::Value::String(args.trim().to_string()),
+            );
+        }
+
+        serde_json::json!({
+            "name": self.name,
+            "options": options,
+        })
+    }
+}
+
+/// The `/net/openvpn/v3/configuration` object.
+pub(crate) struct ConfigurationManager {
+    pub(crate) state: Arc<Mutex<ServerState>>,
+}
+
+#[dbus_interface(name = "net.openvpn.v3.configuration")]
+impl ConfigurationManager {
+    async fn fetch_available_configs(&self) -> Vec<OwnedObjectPath> {
+        let state = self.state.lock().unwrap();
+
+        state
+            .configurations
+            .iter()
+            .map(|config| config.lock().unwrap().path.clone())
+            .collect()
+    }
+
+    async fn import(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+        name: &str,
+        config_str: &str,
+        single_use: bool,
+        persistent: bool,
+    ) -> Result<OwnedObjectPath, ServiceError> {
+        let data = self
+            .state
+            .lock()
+            .unwrap()
+            .add_configuration(name, config_str, single_use, persistent);
+        let path = data.lock().unwrap().path.clone();
+
+        server
+            .at(
+                path.clone(),
+                ConfigurationNode {
+                    state: self.state.clone(),
+                    data,
+                },
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(path)
+    }
+
+    async fn lookup_config_name(&self, config_name: &str) -> Vec<OwnedObjectPath> {
+        let state = self.state.lock().unwrap();
+
+        state
+            .configurations
+            .iter()
+            .filter_map(|config| {
+                let config = config.lock().unwrap();
+                (config.name == config_name).then(|| config.path.clone())
+            })
+            .collect()
+    }
+
+    async fn transfer_ownership(
+        &self,
+        path: ObjectPath<'_>,
+        new_owner_uid: u32,
+    ) -> Result<(), ServiceError> {
+        let config = self
+            .state
+            .lock()
+            .unwrap()
+            .configuration(&path)
+            .ok_or_else(|| ServiceError::configuration("Configuration profile not found"))?;
+
+        config.lock().unwrap().owner = new_owner_uid;
+        Ok(())
+    }
+
+    #[dbus_interface(signal)]
+    pub(crate) async fn log(
+        ctxt: &SignalContext<'_>,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(property, name = "version")]
+    async fn version(&self) -> String {
+        self.state.lock().unwrap().version.clone()
+    }
+}
+
+/// A `/net/openvpn/v3/configuration/<id>` object.
+pub(crate) struct ConfigurationNode {
+    pub(crate) state: Arc<Mutex<ServerState>>,
+    pub(crate) data: Arc<Mutex<ConfigurationData>>,
+}
+
+impl ConfigurationNode {
+    fn modify<F>(&self, f: F) -> Result<(), ServiceError>
+    where
+        F: FnOnce(&mut ConfigurationData),
+    {
+        let mut data = self.data.lock().unwrap();
+
+        if data.sealed {
+            return Err(ServiceError::configuration(
+                "Configuration profile is sealed and read-only",
+            ));
+        }
+
+        f(&mut data);
+        Ok(())
+    }
+}
+
+#[dbus_interface(name = "net.openvpn.v3.configuration")]
+impl ConfigurationNode {
+    async fn access_grant(&self, uid: u32) {
+        let mut data = self.data.lock().unwrap();
+
+        if !data.acl.contains(&uid) {
+            data.acl.push(uid);
+        }
+    }
+
+    async fn access_revoke(&self, uid: u32) {
+        self.data.lock().unwrap().acl.retain(|u| *u != uid);
+    }
+
+    async fn fetch(&self) -> String {
+        self.data.lock().unwrap().config_str.clone()
+    }
+
+    #[dbus_interface(name = "FetchJSON")]
+    async fn fetch_json(&self) -> String {
+        self.data.lock().unwrap().json().to_string()
+    }
+
+    async fn remove(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+    ) -> Result<(), ServiceError> {
+        let path = self.data.lock().unwrap().path.clone();
+
+        self.state
+            .lock()
+            .unwrap()
+            .configurations
+            .retain(|config| !Arc::ptr_eq(config, &self.data));
+
+        server
+            .remove::<ConfigurationNode, _>(&path)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(())
+    }
+
+    async fn seal(&self) {
+        self.data.lock().unwrap().sealed = true;
+    }
+
+    async fn set_option(&self, option: &str, value: &str) -> Result<(), ServiceError> {
+        self.modify(|data| {
+            data.config_str
+                .push_str(&format!("\n{} {}\n", option, value));
+        })
+    }
+
+    async fn set_override(&self, name: &str, value: OwnedValue) -> Result<(), ServiceError> {
+        self.modify(|data| {
+            data.overrides.insert(name.to_string(), value);
+        })
+    }
+
+    async fn unset_override(&self, name: &str) -> Result<(), ServiceError> {
+        let mut data = self.data.lock().unwrap();
+
+        data.overrides
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| ServiceError::configuration("Override not found"))
+    }
+
+    #[dbus_interface(property, name = "acl")]
+    async fn acl(&self) -> Vec<u32> {
+        self.data.lock().unwrap().acl.clone()
+    }
+
+    #[dbus_interface(property, name = "dco")]
+    async fn dco(&self) -> bool {
+        self.data.lock().unwrap().dco
+    }
+
+    #[dbus_interface(property, name = "dco")]
+    async fn set_dco(&mut self, value: bool) {
+        self.data.lock().unwrap().dco = value;
+    }
+
+    #[dbus_interface(property, name = "import_timestamp")]
+    async fn import_timestamp(&self) -> u64 {
+        self.data.lock().unwrap().import_timestamp
+    }
+
+    #[dbus_interface(property, name = "last_used_timestamp")]
+    async fn last_used_timestamp(&self) -> u64 {
+        self.data.lock().unwrap().last_used_timestamp
+    }
+
+    #[dbus_interface(property, name = "locked_down")]
+    async fn locked_down(&self) -> bool {
+        self.data.lock().unwrap().locked_down
+    }
+
+    #[dbus_interface(property, name = "locked_down")]
+    async fn set_locked_down(&mut self, value: bool) {
+        self.data.lock().unwrap().locked_down = value;
+    }
+
+    #[dbus_interface(property, name = "name")]
+    async fn name(&self) -> String {
+        self.data.lock().unwrap().name.clone()
+    }
+
+    #[dbus_interface(property, name = "name")]
+    async fn set_name(&mut self, value: String) {
+        self.data.lock().unwrap().name = value;
+    }
+
+    #[dbus_interface(property, name = "overrides")]
+    async fn overrides(&self) -> HashMap<String, OwnedValue> {
+        self.data.lock().unwrap().overrides.clone()
+    }
+
+    #[dbus_interface(property, name = "owner")]
+    async fn owner(&self) -> u32 {
+        self.data.lock().unwrap().owner
+    }
+
+    #[dbus_interface(property, name = "persistent")]
+    async fn persistent(&self) -> bool {
+        self.data.lock().unwrap().persistent
+    }
+
+    #[dbus_interface(property, name = "public_access")]
+    async fn public_access(&self) -> bool {
+        self.data.lock().unwrap().public_access
+    }
+
+    #[dbus_interface(property, name = "public_access")]
+    async fn set_public_access(&mut self, value: bool) {
+        self.data.lock().unwrap().public_access = value;
+    }
+
+    #[dbus_interface(property, name = "readonly")]
+    async fn readonly(&self) -> bool {
+        self.data.lock().unwrap().sealed
+    }
+
+    #[dbus_interface(property, name = "single_use")]
+    async fn single_use(&self) -> bool {
+        self.data.lock().unwrap().single_use
+    }
+
+    #[dbus_interface(property, name = "transfer_owner_session")]
+    async fn transfer_owner_session(&self) -> bool {
+        self.data.lock().unwrap().transfer_owner_session
+    }
+
+    #[dbus_interface(property, name = "transfer_owner_session")]
+    async fn set_transfer_owner_session(&mut self, value: bool) {
+        self.data.lock().unwrap().transfer_owner_session = value;
+    }
+
+    #[dbus_interface(property, name = "used_count")]
+    async fn used_count(&self) -> u32 {
+        self.data.lock().unwrap().used_count
+    }
+
+    #[dbus_interface(property, name = "valid")]
+    async fn valid(&self) -> bool {
+        true
+    }
+}
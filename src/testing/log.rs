@@ -0,0 +1,167 @@
+//! Mock of the `net.openvpn.v3.log` service.
+
+use super::{ServerState, ServiceError};
+
+use crate::log::constants::LogLevel;
+
+use std::sync::{Arc, Mutex};
+use zbus::{
+    dbus_interface,
+    zvariant::{ObjectPath, OwnedObjectPath},
+    ObjectServer,
+};
+
+/// The `/net/openvpn/v3/log` object.
+pub(crate) struct LogService {
+    pub(crate) state: Arc<Mutex<ServerState>>,
+    pub(crate) attached: Vec<String>,
+    pub(crate) log_level: LogLevel,
+    pub(crate) timestamp: bool,
+    pub(crate) log_dbus_details: bool,
+    pub(crate) log_prefix_logtag: bool,
+}
+
+impl LogService {
+    pub(crate) fn new(state: Arc<Mutex<ServerState>>) -> Self {
+        Self {
+            state,
+            attached: Vec::new(),
+            log_level: LogLevel::INFO,
+            timestamp: true,
+            log_dbus_details: false,
+            log_prefix_logtag: false,
+        }
+    }
+}
+
+#[dbus_interface(name = "net.openvpn.v3.log")]
+impl LogService {
+    async fn attach(&mut self, interface: String) {
+        if !self.attached.contains(&interface) {
+            self.attached.push(interface);
+        }
+    }
+
+    async fn detach(&mut self, interface: &str) {
+        self.attached.retain(|other| other != interface);
+    }
+
+    async fn proxy_log_events(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+        target_address: String,
+        session_path: ObjectPath<'_>,
+    ) -> Result<OwnedObjectPath, ServiceError> {
+        let id = self.state.lock().unwrap().next_id();
+        let path = OwnedObjectPath::try_from(format!("/net/openvpn/v3/log/proxy/mock{}", id))
+            .expect("valid object path");
+
+        server
+            .at(
+                path.clone(),
+                LogNode {
+                    path: path.clone(),
+                    session_path: session_path.into(),
+                    target: target_address,
+                    log_level: self.log_level,
+                },
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(path)
+    }
+
+    #[dbus_interface(property, name = "log_level")]
+    async fn log_level(&self) -> LogLevel {
+        self.log_level
+    }
+
+    #[dbus_interface(property, name = "log_level")]
+    async fn set_log_level(&mut self, value: u32) -> zbus::fdo::Result<()> {
+        self.log_level = log_level(value)?;
+        Ok(())
+    }
+
+    #[dbus_interface(property, name = "timestamp")]
+    async fn timestamp(&self) -> bool {
+        self.timestamp
+    }
+
+    #[dbus_interface(property, name = "timestamp")]
+    async fn set_timestamp(&mut self, value: bool) {
+        self.timestamp = value;
+    }
+
+    #[dbus_interface(property, name = "log_dbus_details")]
+    async fn log_dbus_details(&self) -> bool {
+        self.log_dbus_details
+    }
+
+    #[dbus_interface(property, name = "log_dbus_details")]
+    async fn set_log_dbus_details(&mut self, value: bool) {
+        self.log_dbus_details = value;
+    }
+
+    #[dbus_interface(property, name = "log_prefix_logtag")]
+    async fn log_prefix_logtag(&self) -> bool {
+        self.log_prefix_logtag
+    }
+
+    #[dbus_interface(property, name = "log_prefix_logtag")]
+    async fn set_log_prefix_logtag(&mut self, value: bool) {
+        self.log_prefix_logtag = value;
+    }
+
+    #[dbus_interface(property, name = "num_attached")]
+    async fn num_attached(&self) -> u32 {
+        self.attached.len() as u32
+    }
+
+    #[dbus_interface(property, name = "version")]
+    async fn version(&self) -> String {
+        self.state.lock().unwrap().version.clone()
+    }
+}
+
+/// A `/net/openvpn/v3/log/proxy/<id>` object, created by `ProxyLogEvents`.
+pub(crate) struct LogNode {
+    path: OwnedObjectPath,
+    session_path: OwnedObjectPath,
+    target: String,
+    log_level: LogLevel,
+}
+
+#[dbus_interface(name = "net.openvpn.v3.log")]
+impl LogNode {
+    async fn remove(&self, #[zbus(object_server)] server: &ObjectServer) -> zbus::fdo::Result<()> {
+        server.remove::<LogNode, _>(&self.path).await?;
+        Ok(())
+    }
+
+    #[dbus_interface(property, name = "log_level")]
+    async fn log_level(&self) -> LogLevel {
+        self.log_level
+    }
+
+    #[dbus_interface(property, name = "log_level")]
+    async fn set_log_level(&mut self, value: u32) -> zbus::fdo::Result<()> {
+        self.log_level = log_level(value)?;
+        Ok(())
+    }
+
+    #[dbus_interface(property, name = "session_path")]
+    async fn session_path(&self) -> OwnedObjectPath {
+        self.session_path.clone()
+    }
+
+    #[dbus_interface(property, name = "target")]
+    async fn target(&self) -> String {
+        self.target.clone()
+    }
+}
+
+fn log_level(value: u32) -> zbus::fdo::Result<LogLevel> {
+    LogLevel::try_from(value)
+        .map_err(|_| zbus::fdo::Error::InvalidArgs(format!("Invalid log level {}", value)))
+}
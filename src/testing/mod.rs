@@ -0,0 +1,459 @@
+//! In-process mocks of the OpenVPN 3 D-Bus services, for testing code built on this crate without an openvpn3-linux install.
+//!
+//! Requires the `testing` feature. The mocks are served on a private bus, run by the `dbus-daemon` binary which must be available in `PATH`.
+//!
+//! # Examples
+//!
+//! ```
+//! # openvpn3_rs::testing::block_on(async {
+//! use openvpn3_rs::{
+//!     helpers::CredentialSet,
+//!     testing::{MockServer, SessionScript},
+//! };
+//! use std::time::Duration;
+//!
+//! let server = MockServer::start().await.unwrap();
+//! server.script("My VPN", SessionScript::new().credentials());
+//!
+//! let openvpn3 = server.client().await.unwrap();
+//! let config = openvpn3.import("My VPN", "remote vpn.example.com 1194\n", true, false).await.unwrap();
+//! let session = config.new_tunnel().await.unwrap();
+//!
+//! session
+//!     .connect_with(&CredentialSet::new("smith", "hunter2"), Duration::from_secs(5))
+//!     .await
+//!     .unwrap();
+//!
+//! let mock = server.session(session.path()).unwrap();
+//! assert_eq!(mock.provided_input().len(), 2);
+//! # });
+//! ```
+
+mod configuration;
+mod log;
+mod netcfg;
+mod sessions;
+
+pub use crate::runtime::{block_on, sleep};
+pub use sessions::{MockEvent, MockSession, MockUserInput, SessionScript};
+
+use self::{
+    configuration::{ConfigurationData, ConfigurationManager, ConfigurationNode},
+    log::LogService,
+    netcfg::NetCfgManager,
+    sessions::SessionManager,
+};
+
+use crate::{
+    helpers::{OpenVPN3, Service},
+    log::constants::{LogCategory, LogGroup},
+    Error, Result,
+};
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zbus::{
+    names::ErrorName,
+    zvariant::{ObjectPath, OwnedObjectPath},
+    Connection, ConnectionBuilder, DBusError, Message, MessageBuilder, MessageHeader,
+    SignalContext,
+};
+
+/// Seconds since the UNIX epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// An error returned by a mocked service, using the error names of the OpenVPN 3 services.
+#[derive(Debug)]
+pub(crate) struct ServiceError {
+    name: &'static str,
+    description: String,
+}
+
+impl ServiceError {
+    fn new(name: &'static str, description: &str) -> Self {
+        Self {
+            name,
+            description: description.to_string(),
+        }
+    }
+
+    fn configuration(description: &str) -> Self {
+        Self::new("net.openvpn.v3.configuration.error", description)
+    }
+
+    fn sessions(description: &str) -> Self {
+        Self::new("net.openvpn.v3.sessions.error", description)
+    }
+
+    fn input_queue(description: &str) -> Self {
+        Self::new("net.openvpn.v3.error.input_queue", description)
+    }
+
+    fn ready(description: &str) -> Self {
+        Self::new("net.openvpn.v3.error.ready", description)
+    }
+}
+
+impl From<zbus::Error> for ServiceError {
+    fn from(err: zbus::Error) -> Self {
+        Self::new("org.freedesktop.DBus.Error.Failed", &err.to_string())
+    }
+}
+
+impl DBusError for ServiceError {
+    fn create_reply(&self, msg: &MessageHeader<'_>) -> zbus::Result<Message> {
+        MessageBuilder::error(msg, self.name)?.build(&self.description.as_str())
+    }
+
+    fn name(&self) -> ErrorName<'_> {
+        ErrorName::from_static_str_unchecked(self.name)
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(&self.description)
+    }
+}
+
+/// State shared by all mocked objects.
+#[derive(Debug)]
+pub(crate) struct ServerState {
+    version: String,
+    next_id: u32,
+    configurations: Vec<Arc<Mutex<ConfigurationData>>>,
+    sessions: Vec<MockSession>,
+    scripts: HashMap<String, SessionScript>,
+    default_script: SessionScript,
+}
+
+impl ServerState {
+    fn next_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn add_configuration(
+        &mut self,
+        name: &str,
+        config_str: &str,
+        single_use: bool,
+        persistent: bool,
+    ) -> Arc<Mutex<ConfigurationData>> {
+        let path = format!("/net/openvpn/v3/configuration/mock{}", self.next_id());
+        let data = Arc::new(Mutex::new(ConfigurationData::new(
+            OwnedObjectPath::try_from(path).expect("valid object path"),
+            name,
+            config_str,
+            single_use,
+            persistent,
+        )));
+
+        self.configurations.push(data.clone());
+        data
+    }
+
+    fn configuration(&self, path: &ObjectPath<'_>) -> Option<Arc<Mutex<ConfigurationData>>> {
+        self.configurations
+            .iter()
+            .find(|config| config.lock().unwrap().path.as_ref() == *path)
+            .cloned()
+    }
+
+    fn new_session(
+        &mut self,
+        connection: &Connection,
+        config_path: &ObjectPath<'_>,
+    ) -> Option<MockSession> {
+        let config = self.configuration(config_path)?;
+        let (config_name, single_use) = {
+            let mut config = config.lock().unwrap();
+            config.used_count += 1;
+            config.last_used_timestamp = now();
+            (config.name.clone(), config.single_use)
+        };
+
+        if single_use {
+            self.configurations
+                .retain(|other| !Arc::ptr_eq(other, &config));
+        }
+
+        let path = format!("/net/openvpn/v3/sessions/mock{}", self.next_id());
+        let session = MockSession::new(
+            connection.clone(),
+            OwnedObjectPath::try_from(path).expect("valid object path"),
+            OwnedObjectPath::from(config_path.to_owned()),
+            &config_name,
+            self.scripts
+                .get(&config_name)
+                .unwrap_or(&self.default_script)
+                .clone(),
+        );
+
+        self.sessions.push(session.clone());
+        Some(session)
+    }
+
+    fn session(&self, path: &ObjectPath<'_>) -> Option<MockSession> {
+        self.sessions
+            .iter()
+            .find(|session| session.path().as_ref() == *path)
+            .cloned()
+    }
+}
+
+/// Mock Bus
+///
+/// A private message bus, run by a `dbus-daemon` child process which is killed when this is dropped.
+#[derive(Debug)]
+pub struct MockBus {
+    daemon: Child,
+    address: String,
+}
+
+impl MockBus {
+    /// Start a private message bus.
+    pub fn start() -> Result<Self> {
+        let mut daemon = Command::new("dbus-daemon")
+            .args([
+                "--session",
+                "--nofork",
+                "--print-address",
+                "--address=unix:tmpdir=/tmp",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let mut address = String::new();
+        if let Some(stdout) = daemon.stdout.take() {
+            BufReader::new(stdout).read_line(&mut address)?;
+        }
+
+        let address = address.trim().to_string();
+        if address.is_empty() {
+            let _ = daemon.kill();
+            return Err(Error::Io(std::io::Error::other(
+                "dbus-daemon did not report its address",
+            )));
+        }
+
+        Ok(Self { daemon, address })
+    }
+
+    /// Address of the bus.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Open a new connection to the bus.
+    pub async fn connect(&self) -> Result<Connection> {
+        Ok(ConnectionBuilder::address(self.address())?.build().await?)
+    }
+}
+
+impl Drop for MockBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+/// Mock Server
+///
+/// Serves mocks of the `net.openvpn.v3.configuration` and `net.openvpn.v3.sessions` services, and their configuration profile and session objects, on a [MockBus]. Minimal mocks of the `net.openvpn.v3.log` and `net.openvpn.v3.netcfg` services are served as well.
+///
+/// The behaviour of sessions is scripted per configuration profile with a [SessionScript], and signals can be emitted on behalf of a session through its [MockSession] handle.
+#[derive(Debug)]
+pub struct MockServer {
+    connection: Connection,
+    state: Arc<Mutex<ServerState>>,
+    bus: MockBus,
+}
+
+impl MockServer {
+    /// Start a [MockBus] and serve the mocked services on it.
+    pub async fn start() -> Result<Self> {
+        let bus = MockBus::start()?;
+        let connection = bus.connect().await?;
+        let state = Arc::new(Mutex::new(ServerState {
+            version: String::from("v21"),
+            next_id: 0,
+            configurations: Vec::new(),
+            sessions: Vec::new(),
+            scripts: HashMap::new(),
+            default_script: SessionScript::default(),
+        }));
+
+        connection
+            .object_server()
+            .at(
+                Service::Configuration.path(),
+                ConfigurationManager {
+                    state: state.clone(),
+                },
+            )
+            .await?;
+        connection
+            .object_server()
+            .at(
+                Service::Sessions.path(),
+                SessionManager {
+                    connection: connection.clone(),
+                    state: state.clone(),
+                },
+            )
+            .await?;
+        connection
+            .object_server()
+            .at(Service::Log.path(), LogService::new(state.clone()))
+            .await?;
+        connection
+            .object_server()
+            .at(
+                Service::NetCfg.path(),
+                NetCfgManager {
+                    state: state.clone(),
+                },
+            )
+            .await?;
+
+        for service in Service::all() {
+            connection
+                .request_name(service.default_destination())
+                .await?;
+        }
+
+        Ok(Self {
+            connection,
+            state,
+            bus,
+        })
+    }
+
+    /// Address of the private bus.
+    pub fn address(&self) -> &str {
+        self.bus.address()
+    }
+
+    /// Open a new client connection to the private bus.
+    pub async fn connection(&self) -> Result<Connection> {
+        self.bus.connect().await
+    }
+
+    /// Construct an [OpenVPN3] client connected to the private bus.
+    pub async fn client(&self) -> Result<OpenVPN3<'static>> {
+        OpenVPN3::new(self.connection().await?).await
+    }
+
+    /// Set the version reported by the services.
+    pub fn set_version(&self, version: &str) {
+        self.state.lock().unwrap().version = version.to_string();
+    }
+
+    /// Stop serving a service, by releasing its bus name.
+    pub async fn stop_service(&self, service: Service) -> Result<()> {
+        self.connection
+            .release_name(service.default_destination())
+            .await?;
+        Ok(())
+    }
+
+    /// Set the [SessionScript] for sessions started from a configuration profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - Name of the configuration profile.
+    /// * `script` - Behaviour of the sessions.
+    pub fn script(&self, config_name: &str, script: SessionScript) {
+        self.state
+            .lock()
+            .unwrap()
+            .scripts
+            .insert(config_name.to_string(), script);
+    }
+
+    /// Set the [SessionScript] for sessions started from profiles without their own script.
+    pub fn default_script(&self, script: SessionScript) {
+        self.state.lock().unwrap().default_script = script;
+    }
+
+    /// Add a configuration profile, as if it had been imported.
+    ///
+    /// # Returns
+    ///
+    /// The D-Bus object path of the configuration profile.
+    pub async fn add_configuration(&self, name: &str, config_str: &str) -> Result<OwnedObjectPath> {
+        let data = self
+            .state
+            .lock()
+            .unwrap()
+            .add_configuration(name, config_str, false, false);
+        let path = data.lock().unwrap().path.clone();
+
+        self.connection
+            .object_server()
+            .at(
+                path.clone(),
+                ConfigurationNode {
+                    state: self.state.clone(),
+                    data,
+                },
+            )
+            .await?;
+
+        Ok(path)
+    }
+
+    /// Handles to all sessions which have not been disconnected.
+    pub fn sessions(&self) -> Vec<MockSession> {
+        self.state.lock().unwrap().sessions.clone()
+    }
+
+    /// Handle to the session with the given D-Bus object path.
+    pub fn session(&self, path: &ObjectPath<'_>) -> Option<MockSession> {
+        self.state.lock().unwrap().session(path)
+    }
+
+    /// Emit a `Log` signal from the session manager.
+    pub async fn emit_sessions_log(
+        &self,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> Result<()> {
+        let ctxt = SignalContext::new(&self.connection, Service::Sessions.path())?;
+        Ok(SessionManager::log(&ctxt, group, category, message).await?)
+    }
+
+    /// Emit a `Log` signal from the configuration manager.
+    pub async fn emit_configuration_log(
+        &self,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> Result<()> {
+        let ctxt = SignalContext::new(&self.connection, Service::Configuration.path())?;
+        Ok(ConfigurationManager::log(&ctxt, group, category, message).await?)
+    }
+
+    /// Emit a `Log` signal from the network configuration service.
+    pub async fn emit_netcfg_log(
+        &self,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> Result<()> {
+        let ctxt = SignalContext::new(&self.connection, Service::NetCfg.path())?;
+        Ok(NetCfgManager::log(&ctxt, group, category, message).await?)
+    }
+}
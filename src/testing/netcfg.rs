@@ -0,0 +1,37 @@
+//! Mock of the `net.openvpn.v3.netcfg` service.
+
+use super::ServerState;
+
+use crate::log::constants::{LogCategory, LogGroup};
+
+use std::sync::{Arc, Mutex};
+use zbus::{dbus_interface, zvariant::OwnedObjectPath, SignalContext};
+
+/// The `/net/openvpn/v3/netcfg` object.
+pub(crate) struct NetCfgManager {
+    pub(crate) state: Arc<Mutex<ServerState>>,
+}
+
+#[dbus_interface(name = "net.openvpn.v3.netcfg")]
+impl NetCfgManager {
+    async fn dco_available(&self) -> bool {
+        false
+    }
+
+    async fn fetch_interface_list(&self) -> Vec<OwnedObjectPath> {
+        Vec::new()
+    }
+
+    #[dbus_interface(signal)]
+    pub(crate) async fn log(
+        ctxt: &SignalContext<'_>,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(property, name = "version")]
+    async fn version(&self) -> String {
+        self.state.lock().unwrap().version.clone()
+    }
+}
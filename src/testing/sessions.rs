@@ -0,0 +1,918 @@
+//! Mock of the `net.openvpn.v3.sessions` service.
+
+use super::{now, ServerState, ServiceError};
+
+use crate::{
+    log::constants::{LogCategory, LogGroup, LogLevel},
+    runtime,
+    sessions::constants::EventType,
+    sessions_node::{
+        constants::{ClientAttentionGroup, ClientAttentionType, StatusMajor, StatusMinor},
+        result::Statistics,
+    },
+};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use zbus::{
+    dbus_interface,
+    zvariant::{ObjectPath, OwnedObjectPath},
+    Connection, ObjectServer, SignalContext,
+};
+
+/// Mock Event
+///
+/// A signal emitted by a [MockSession], or a pause between signals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockEvent {
+    /// Update the `status` property and emit a `StatusChange` signal.
+    Status(StatusMajor, StatusMinor, String),
+    /// Emit an `AttentionRequired` signal.
+    Attention(ClientAttentionType, ClientAttentionGroup, String),
+    /// Update the `last_log` property and emit a `Log` signal.
+    Log(LogGroup, LogCategory, String),
+    /// Queue a request for user input and emit an `AttentionRequired` signal for it.
+    RequireInput(MockUserInput),
+    /// Wait before emitting the next event.
+    Delay(Duration),
+}
+
+impl MockEvent {
+    /// Construct a [MockEvent::Status] event.
+    pub fn status(code_major: StatusMajor, code_minor: StatusMinor, message: &str) -> Self {
+        Self::Status(code_major, code_minor, message.to_string())
+    }
+
+    /// Construct a [MockEvent::Attention] event.
+    pub fn attention(
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        message: &str,
+    ) -> Self {
+        Self::Attention(qtype, qgroup, message.to_string())
+    }
+
+    /// Construct a [MockEvent::Log] event.
+    pub fn log(group: LogGroup, category: LogCategory, message: &str) -> Self {
+        Self::Log(group, category, message.to_string())
+    }
+}
+
+/// Mock User Input
+///
+/// A request for user input queued by a [MockSession].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockUserInput {
+    qtype: ClientAttentionType,
+    qgroup: ClientAttentionGroup,
+    variable_name: String,
+    label: String,
+    mask: bool,
+}
+
+impl MockUserInput {
+    /// Construct a [MockUserInput].
+    ///
+    /// # Arguments
+    ///
+    /// * `qtype` - [ClientAttentionType] of the request.
+    /// * `qgroup` - [ClientAttentionGroup] of the request.
+    /// * `variable_name` - Internal variable name.
+    /// * `label` - Description presented to the user.
+    /// * `mask` - Should the user's input be masked?
+    pub fn new(
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        variable_name: &str,
+        label: &str,
+        mask: bool,
+    ) -> Self {
+        Self {
+            qtype,
+            qgroup,
+            variable_name: variable_name.to_string(),
+            label: label.to_string(),
+            mask,
+        }
+    }
+
+    /// Request a username.
+    pub fn username() -> Self {
+        Self::new(
+            ClientAttentionType::Credentials,
+            ClientAttentionGroup::UserPassword,
+            "username",
+            "Auth User name",
+            false,
+        )
+    }
+
+    /// Request a password.
+    pub fn password() -> Self {
+        Self::new(
+            ClientAttentionType::Credentials,
+            ClientAttentionGroup::UserPassword,
+            "password",
+            "Auth Password",
+            true,
+        )
+    }
+
+    /// Request a response to a static challenge.
+    pub fn static_challenge(prompt: &str, echo: bool) -> Self {
+        Self::new(
+            ClientAttentionType::Credentials,
+            ClientAttentionGroup::ChallengeStatic,
+            "static_challenge",
+            prompt,
+            !echo,
+        )
+    }
+
+    /// Request a response to a dynamic challenge, e.g. `CRV1:R,E:<state_id>:<base64_username>:<text>`.
+    pub fn dynamic_challenge(challenge: &str) -> Self {
+        Self::new(
+            ClientAttentionType::Credentials,
+            ClientAttentionGroup::ChallengeDynamic,
+            "dynamic_challenge",
+            challenge,
+            false,
+        )
+    }
+}
+
+/// Session Script
+///
+/// Describes how the [MockSession]s created for a configuration profile behave.
+///
+/// By default a session requires no user input, is ready immediately, connects when `Connect` is called and reports being disconnected when `Disconnect` is called.
+#[derive(Clone, Debug, Default)]
+pub struct SessionScript {
+    user_input: Vec<MockUserInput>,
+    not_ready: u32,
+    on_connect: Vec<Vec<MockEvent>>,
+    on_disconnect: Option<Vec<MockEvent>>,
+}
+
+impl SessionScript {
+    /// Construct a [SessionScript] with the default behaviour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a request for user input when the session is created.
+    pub fn user_input(mut self, input: MockUserInput) -> Self {
+        self.user_input.push(input);
+        self
+    }
+
+    /// Request a username and password when the session is created.
+    pub fn credentials(self) -> Self {
+        self.user_input(MockUserInput::username())
+            .user_input(MockUserInput::password())
+    }
+
+    /// Answer the first `count` `Ready` calls with a "backend not ready" error.
+    pub fn not_ready(mut self, count: u32) -> Self {
+        self.not_ready = count;
+        self
+    }
+
+    /// Add a stage of events emitted when `Connect` is called.
+    ///
+    /// The first `Connect` call emits the first stage, the second call the second stage, and so on. Once all stages have been used, the last stage is repeated.
+    pub fn on_connect(mut self, events: Vec<MockEvent>) -> Self {
+        self.on_connect.push(events);
+        self
+    }
+
+    /// Set the events emitted when `Disconnect` is called, before the session is removed.
+    pub fn on_disconnect(mut self, events: Vec<MockEvent>) -> Self {
+        self.on_disconnect = Some(events);
+        self
+    }
+
+    fn connect_stage(&self, count: usize) -> Vec<MockEvent> {
+        match self.on_connect.len() {
+            0 => vec![
+                MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnecting, ""),
+                MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnected, ""),
+            ],
+            len => self.on_connect[count.min(len - 1)].clone(),
+        }
+    }
+
+    fn disconnect_stage(&self) -> Vec<MockEvent> {
+        match &self.on_disconnect {
+            Some(events) => events.clone(),
+            None => vec![MockEvent::status(
+                StatusMajor::CONNECTION,
+                StatusMinor::ConnDisconnected,
+                "",
+            )],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InputSlot {
+    id: u32,
+    input: MockUserInput,
+    value: Option<String>,
+}
+
+/// State of a mocked VPN session.
+#[derive(Debug)]
+pub(crate) struct SessionData {
+    path: OwnedObjectPath,
+    config_path: OwnedObjectPath,
+    config_name: String,
+    device_name: String,
+    created: u64,
+    owner: u32,
+    acl: Vec<u32>,
+    status: (StatusMajor, StatusMinor, String),
+    last_log: (LogGroup, LogCategory, String),
+    statistics: Statistics,
+    dco: bool,
+    public_access: bool,
+    restrict_log_access: bool,
+    log_verbosity: u32,
+    log_forward: bool,
+    inputs: Vec<InputSlot>,
+    provided: Vec<(ClientAttentionGroup, String, String)>,
+    script: SessionScript,
+    not_ready: u32,
+    connect_count: usize,
+}
+
+impl SessionData {
+    fn new(
+        path: OwnedObjectPath,
+        config_path: OwnedObjectPath,
+        config_name: &str,
+        script: SessionScript,
+    ) -> Self {
+        let mut data = Self {
+            path,
+            config_path,
+            config_name: config_name.to_string(),
+            device_name: String::new(),
+            created: now(),
+            owner: 0,
+            acl: Vec::new(),
+            status: (StatusMajor::CONFIG, StatusMinor::CfgOk, String::new()),
+            last_log: (LogGroup::UNDEFINED, LogCategory::UNDEFINED, String::new()),
+            statistics: Statistics::new(),
+            dco: false,
+            public_access: false,
+            restrict_log_access: false,
+            log_verbosity: LogLevel::INFO as u32,
+            log_forward: false,
+            inputs: Vec::new(),
+            provided: Vec::new(),
+            not_ready: script.not_ready,
+            script,
+            connect_count: 0,
+        };
+
+        for input in data.script.user_input.clone() {
+            data.require_input(input);
+        }
+
+        data
+    }
+
+    fn require_input(&mut self, input: MockUserInput) -> u32 {
+        let id = self.inputs.len() as u32;
+        self.inputs.push(InputSlot {
+            id,
+            input,
+            value: None,
+        });
+        self.status = (
+            StatusMajor::CONFIG,
+            StatusMinor::CfgRequireUser,
+            String::from("Missing user credentials"),
+        );
+        id
+    }
+
+    fn pending(&self) -> impl Iterator<Item = &InputSlot> {
+        self.inputs.iter().filter(|slot| slot.value.is_none())
+    }
+
+    fn slot(
+        &mut self,
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        id: u32,
+    ) -> Option<&mut InputSlot> {
+        self.inputs
+            .iter_mut()
+            .find(|slot| slot.id == id && slot.input.qtype == qtype && slot.input.qgroup == qgroup)
+    }
+}
+
+/// Mock Session
+///
+/// A handle to a mocked VPN session, which can be used to inspect it and emit signals on its behalf.
+#[derive(Clone, Debug)]
+pub struct MockSession {
+    connection: Connection,
+    data: Arc<Mutex<SessionData>>,
+}
+
+impl MockSession {
+    pub(crate) fn new(
+        connection: Connection,
+        path: OwnedObjectPath,
+        config_path: OwnedObjectPath,
+        config_name: &str,
+        script: SessionScript,
+    ) -> Self {
+        Self {
+            connection,
+            data: Arc::new(Mutex::new(SessionData::new(
+                path,
+                config_path,
+                config_name,
+                script,
+            ))),
+        }
+    }
+
+    /// D-Bus object path of the session.
+    pub fn path(&self) -> OwnedObjectPath {
+        self.data.lock().unwrap().path.clone()
+    }
+
+    /// Name of the configuration profile the session was started with.
+    pub fn config_name(&self) -> String {
+        self.data.lock().unwrap().config_name.clone()
+    }
+
+    /// Number of times `Connect` has been called.
+    pub fn connect_count(&self) -> usize {
+        self.data.lock().unwrap().connect_count
+    }
+
+    /// Has log forwarding been enabled through `LogForward`?
+    pub fn log_forwarding(&self) -> bool {
+        self.data.lock().unwrap().log_forward
+    }
+
+    /// All values provided through `UserInputProvide`, as `(group, variable_name, value)` tuples.
+    pub fn provided_input(&self) -> Vec<(ClientAttentionGroup, String, String)> {
+        self.data.lock().unwrap().provided.clone()
+    }
+
+    /// Set the name of the virtual network interface, reported once connected.
+    pub fn set_device_name(&self, device_name: &str) {
+        self.data.lock().unwrap().device_name = device_name.to_string();
+    }
+
+    /// Set the tunnel statistics.
+    pub fn set_statistics(&self, statistics: Statistics) {
+        self.data.lock().unwrap().statistics = statistics;
+    }
+
+    /// Queue a request for user input, without emitting a signal.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the queued request.
+    pub fn require_input(&self, input: MockUserInput) -> u32 {
+        self.data.lock().unwrap().require_input(input)
+    }
+
+    /// Emit a single [MockEvent].
+    pub async fn emit(&self, event: MockEvent) -> crate::Result<()> {
+        let path = self.path();
+        let ctxt = SignalContext::new(&self.connection, path.as_ref())?;
+
+        match event {
+            MockEvent::Status(code_major, code_minor, message) => {
+                self.data.lock().unwrap().status = (code_major, code_minor, message.clone());
+                SessionNode::status_change(&ctxt, code_major, code_minor, &message).await?;
+            }
+            MockEvent::Attention(qtype, qgroup, message) => {
+                SessionNode::attention_required(&ctxt, qtype, qgroup, &message).await?;
+            }
+            MockEvent::Log(group, category, message) => {
+                self.data.lock().unwrap().last_log = (group, category, message.clone());
+                SessionNode::log(&ctxt, group, category, &message).await?;
+            }
+            MockEvent::RequireInput(input) => {
+                let (qtype, qgroup, label) = (input.qtype, input.qgroup, input.label.clone());
+                self.require_input(input);
+                SessionNode::attention_required(&ctxt, qtype, qgroup, &label).await?;
+            }
+            MockEvent::Delay(duration) => runtime::sleep(duration).await,
+        }
+
+        Ok(())
+    }
+
+    /// Emit a sequence of [MockEvent]s, in order.
+    pub async fn emit_all(&self, events: Vec<MockEvent>) -> crate::Result<()> {
+        for event in events {
+            self.emit(event).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the `status` property and emit a `StatusChange` signal.
+    pub async fn emit_status(
+        &self,
+        code_major: StatusMajor,
+        code_minor: StatusMinor,
+        message: &str,
+    ) -> crate::Result<()> {
+        self.emit(MockEvent::status(code_major, code_minor, message))
+            .await
+    }
+
+    /// Emit an `AttentionRequired` signal.
+    pub async fn emit_attention(
+        &self,
+        qtype: ClientAttentionType,
+        qgroup: ClientAttentionGroup,
+        message: &str,
+    ) -> crate::Result<()> {
+        self.emit(MockEvent::attention(qtype, qgroup, message))
+            .await
+    }
+
+    /// Update the `last_log` property and emit a `Log` signal.
+    pub async fn emit_log(
+        &self,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> crate::Result<()> {
+        self.emit(MockEvent::log(group, category, message)).await
+    }
+
+    /// Emit events in the background, so the method call that triggered them can return first.
+    fn spawn_events(&self, events: Vec<MockEvent>) {
+        let session = self.clone();
+
+        runtime::spawn(async move {
+            let _ = session.emit_all(events).await;
+        });
+    }
+}
+
+/// The `/net/openvpn/v3/sessions` object.
+pub(crate) struct SessionManager {
+    pub(crate) connection: Connection,
+    pub(crate) state: Arc<Mutex<ServerState>>,
+}
+
+#[dbus_interface(name = "net.openvpn.v3.sessions")]
+impl SessionManager {
+    async fn fetch_available_sessions(&self) -> Vec<OwnedObjectPath> {
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .map(MockSession::path)
+            .collect()
+    }
+
+    async fn fetch_managed_interfaces(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .map(|session| session.data.lock().unwrap().device_name.clone())
+            .filter(|device_name| !device_name.is_empty())
+            .collect()
+    }
+
+    async fn lookup_config_name(&self, config_name: &str) -> Vec<OwnedObjectPath> {
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .filter(|session| session.config_name() == config_name)
+            .map(MockSession::path)
+            .collect()
+    }
+
+    async fn lookup_interface(&self, device_name: &str) -> Result<OwnedObjectPath, ServiceError> {
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .find(|session| session.data.lock().unwrap().device_name == device_name)
+            .map(MockSession::path)
+            .ok_or_else(|| ServiceError::sessions("Interface not found"))
+    }
+
+    async fn new_tunnel(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+        config_path: ObjectPath<'_>,
+    ) -> Result<OwnedObjectPath, ServiceError> {
+        let session = self
+            .state
+            .lock()
+            .unwrap()
+            .new_session(&self.connection, &config_path)
+            .ok_or_else(|| ServiceError::sessions("Configuration profile not found"))?;
+        let path = session.path();
+
+        server
+            .at(
+                path.clone(),
+                SessionNode {
+                    state: self.state.clone(),
+                    session,
+                },
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        Self::session_manager_event(&ctxt, path.as_ref(), EventType::SessCreated, 0)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(path)
+    }
+
+    async fn transfer_ownership(
+        &self,
+        path: ObjectPath<'_>,
+        new_owner_uid: u32,
+    ) -> Result<(), ServiceError> {
+        let session = self
+            .state
+            .lock()
+            .unwrap()
+            .session(&path)
+            .ok_or_else(|| ServiceError::sessions("Session not found"))?;
+
+        session.data.lock().unwrap().owner = new_owner_uid;
+        Ok(())
+    }
+
+    #[dbus_interface(signal)]
+    pub(crate) async fn log(
+        ctxt: &SignalContext<'_>,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    pub(crate) async fn session_manager_event(
+        ctxt: &SignalContext<'_>,
+        path: ObjectPath<'_>,
+        event_type: EventType,
+        owner: u32,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(property, name = "version")]
+    async fn version(&self) -> String {
+        self.state.lock().unwrap().version.clone()
+    }
+}
+
+/// A `/net/openvpn/v3/sessions/<id>` object.
+pub(crate) struct SessionNode {
+    state: Arc<Mutex<ServerState>>,
+    session: MockSession,
+}
+
+impl SessionNode {
+    fn data(&self) -> std::sync::MutexGuard<'_, SessionData> {
+        self.session.data.lock().unwrap()
+    }
+}
+
+#[dbus_interface(name = "net.openvpn.v3.sessions")]
+impl SessionNode {
+    async fn access_grant(&self, uid: u32) {
+        let mut data = self.data();
+
+        if !data.acl.contains(&uid) {
+            data.acl.push(uid);
+        }
+    }
+
+    async fn access_revoke(&self, uid: u32) {
+        self.data().acl.retain(|u| *u != uid);
+    }
+
+    async fn connect(&self) -> Result<(), ServiceError> {
+        let events = {
+            let mut data = self.data();
+
+            if data.pending().next().is_some() {
+                return Err(ServiceError::ready("Missing user credentials"));
+            }
+
+            let events = data.script.connect_stage(data.connect_count);
+            data.connect_count += 1;
+            events
+        };
+
+        self.session.spawn_events(events);
+        Ok(())
+    }
+
+    async fn disconnect(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+    ) -> Result<(), ServiceError> {
+        let (path, events) = {
+            let data = self.data();
+            (data.path.clone(), data.script.disconnect_stage())
+        };
+
+        self.session
+            .emit_all(events)
+            .await
+            .map_err(|err| ServiceError::sessions(&err.to_string()))?;
+
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .retain(|session| !Arc::ptr_eq(&session.data, &self.session.data));
+
+        server
+            .remove::<SessionNode, _>(&path)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let ctxt = SignalContext::new(&self.session.connection, "/net/openvpn/v3/sessions")
+            .map_err(ServiceError::from)?;
+        SessionManager::session_manager_event(&ctxt, path.as_ref(), EventType::SessDestroyed, 0)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(())
+    }
+
+    async fn log_forward(&self, enable: bool) {
+        self.data().log_forward = enable;
+    }
+
+    async fn pause(&self, reason: &str) {
+        self.session.spawn_events(vec![
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnPausing, ""),
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnPaused, reason),
+        ]);
+    }
+
+    async fn ready(&self) -> Result<(), ServiceError> {
+        let mut data = self.data();
+
+        if data.not_ready > 0 {
+            data.not_ready -= 1;
+            return Err(ServiceError::sessions("Backend VPN process is not ready"));
+        }
+
+        if data.pending().next().is_some() {
+            return Err(ServiceError::ready("Missing user credentials"));
+        }
+
+        Ok(())
+    }
+
+    async fn restart(&self) {
+        self.session.spawn_events(vec![
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnReconnecting, ""),
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnected, ""),
+        ]);
+    }
+
+    async fn resume(&self) {
+        self.session.spawn_events(vec![
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnResuming, ""),
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnected, ""),
+        ]);
+    }
+
+    async fn user_input_provide(
+        &self,
+        type_: ClientAttentionType,
+        group: ClientAttentionGroup,
+        id: u32,
+        value: &str,
+    ) -> Result<(), ServiceError> {
+        let mut data = self.data();
+
+        let slot = data
+            .slot(type_, group, id)
+            .ok_or_else(|| ServiceError::input_queue("No such user input request"))?;
+        slot.value = Some(value.to_string());
+        let variable_name = slot.input.variable_name.clone();
+
+        data.provided
+            .push((group, variable_name, value.to_string()));
+        Ok(())
+    }
+
+    async fn user_input_queue_check(
+        &self,
+        type_: ClientAttentionType,
+        group: ClientAttentionGroup,
+    ) -> Vec<u32> {
+        self.data()
+            .pending()
+            .filter(|slot| slot.input.qtype == type_ && slot.input.qgroup == group)
+            .map(|slot| slot.id)
+            .collect()
+    }
+
+    async fn user_input_queue_fetch(
+        &self,
+        type_: ClientAttentionType,
+        group: ClientAttentionGroup,
+        id: u32,
+    ) -> Result<
+        (
+            ClientAttentionType,
+            ClientAttentionGroup,
+            u32,
+            String,
+            String,
+            bool,
+        ),
+        ServiceError,
+    > {
+        let mut data = self.data();
+
+        let slot = data
+            .slot(type_, group, id)
+            .ok_or_else(|| ServiceError::input_queue("No such user input request"))?;
+
+        Ok((
+            slot.input.qtype,
+            slot.input.qgroup,
+            slot.id,
+            slot.input.variable_name.clone(),
+            slot.input.label.clone(),
+            slot.input.mask,
+        ))
+    }
+
+    async fn user_input_queue_get_type_group(
+        &self,
+    ) -> Vec<(ClientAttentionType, ClientAttentionGroup)> {
+        let mut type_groups = Vec::new();
+
+        for slot in self.data().pending() {
+            let type_group = (slot.input.qtype, slot.input.qgroup);
+            if !type_groups.contains(&type_group) {
+                type_groups.push(type_group);
+            }
+        }
+
+        type_groups
+    }
+
+    #[dbus_interface(signal)]
+    async fn attention_required(
+        ctxt: &SignalContext<'_>,
+        type_: ClientAttentionType,
+        group: ClientAttentionGroup,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    async fn log(
+        ctxt: &SignalContext<'_>,
+        group: LogGroup,
+        category: LogCategory,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(signal)]
+    async fn status_change(
+        ctxt: &SignalContext<'_>,
+        code_major: StatusMajor,
+        code_minor: StatusMinor,
+        message: &str,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(property, name = "acl")]
+    async fn acl(&self) -> Vec<u32> {
+        self.data().acl.clone()
+    }
+
+    #[dbus_interface(property, name = "backend_pid")]
+    async fn backend_pid(&self) -> u32 {
+        std::process::id()
+    }
+
+    #[dbus_interface(property, name = "config_name")]
+    async fn config_name(&self) -> String {
+        self.data().config_name.clone()
+    }
+
+    #[dbus_interface(property, name = "config_path")]
+    async fn config_path(&self) -> OwnedObjectPath {
+        self.data().config_path.clone()
+    }
+
+    #[dbus_interface(property, name = "dco")]
+    async fn dco(&self) -> bool {
+        self.data().dco
+    }
+
+    #[dbus_interface(property, name = "dco")]
+    async fn set_dco(&mut self, value: bool) {
+        self.data().dco = value;
+    }
+
+    #[dbus_interface(property, name = "device_name")]
+    async fn device_name(&self) -> String {
+        self.data().device_name.clone()
+    }
+
+    #[dbus_interface(property, name = "device_path")]
+    async fn device_path(&self) -> String {
+        String::new()
+    }
+
+    #[dbus_interface(property, name = "last_log")]
+    async fn last_log(&self) -> (u32, u32, String) {
+        let (group, category, message) = self.data().last_log.clone();
+        (group as u32, category as u32, message)
+    }
+
+    #[dbus_interface(property, name = "log_forwards")]
+    async fn log_forwards(&self) -> Vec<OwnedObjectPath> {
+        Vec::new()
+    }
+
+    #[dbus_interface(property, name = "log_verbosity")]
+    async fn log_verbosity(&self) -> u32 {
+        self.data().log_verbosity
+    }
+
+    #[dbus_interface(property, name = "log_verbosity")]
+    async fn set_log_verbosity(&mut self, value: u32) {
+        self.data().log_verbosity = value;
+    }
+
+    #[dbus_interface(property, name = "owner")]
+    async fn owner(&self) -> u32 {
+        self.data().owner
+    }
+
+    #[dbus_interface(property, name = "public_access")]
+    async fn public_access(&self) -> bool {
+        self.data().public_access
+    }
+
+    #[dbus_interface(property, name = "public_access")]
+    async fn set_public_access(&mut self, value: bool) {
+        self.data().public_access = value;
+    }
+
+    #[dbus_interface(property, name = "restrict_log_access")]
+    async fn restrict_log_access(&self) -> bool {
+        self.data().restrict_log_access
+    }
+
+    #[dbus_interface(property, name = "restrict_log_access")]
+    async fn set_restrict_log_access(&mut self, value: bool) {
+        self.data().restrict_log_access = value;
+    }
+
+    #[dbus_interface(property, name = "session_created")]
+    async fn session_created(&self) -> u64 {
+        self.data().created
+    }
+
+    #[dbus_interface(property, name = "session_name")]
+    async fn session_name(&self) -> String {
+        self.data().device_name.clone()
+    }
+
+    #[dbus_interface(property, name = "statistics")]
+    async fn statistics(&self) -> HashMap<String, i64> {
+        self.data().statistics.clone()
+    }
+
+    #[dbus_interface(property, name = "status")]
+    async fn status(&self) -> (u32, u32, String) {
+        let (code_major, code_minor, message) = self.data().status.clone();
+        (code_major as u32, code_minor as u32, message)
+    }
+}
@@ -0,0 +1,1050 @@
+use futures_util::StreamExt;
+use openvpn3_rs::{
+    helpers::{
+        blocking, AccessEntry, Compression, ConfigOverride, CredentialSet, Ipv6Mode, LogFilter,
+        LogReader, LogRecorder, LogSource, OpenVPN3, OverrideKind, Service, ServiceVersion,
+        Session, SessionState, StatisticsSampler, User, WebAuthRequest,
+    },
+    log::constants::{LogCategory, LogGroup, LogLevel},
+    profile::{AuthUserPass, Material, Profile, Proto, Route},
+    sessions_node::constants::{
+        ClientAttentionGroup, ClientAttentionType, StatusMajor, StatusMinor,
+    },
+    testing::{block_on, sleep, MockEvent, MockServer, MockUserInput, SessionScript},
+    Error,
+};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use zbus::zvariant::{OwnedValue, Str};
+
+const CONFIG: &str = "client\nremote vpn.example.com 1194\n";
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn import_and_remove_configuration() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let config = openvpn3.import("test", CONFIG, false, false).await.unwrap();
+        assert_eq!(config.fetch().await.unwrap(), CONFIG);
+        assert_eq!(config.get_property::<String>("name").await.unwrap(), "test");
+        assert_eq!(openvpn3.configurations().await.unwrap().len(), 1);
+
+        config.remove().await.unwrap();
+        assert!(openvpn3.configurations().await.unwrap().is_empty());
+    })
+}
+
+#[test]
+fn builder_with_address() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = OpenVPN3::builder()
+            .address(server.address())
+            .destination(Service::Sessions, "net.openvpn.v3.sessions")
+            .timeout(TIMEOUT)
+            .build()
+            .await
+            .unwrap();
+
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        let session = Session::new(
+            openvpn3.connection().clone(),
+            session.path().to_owned().into(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(session.config_name().await.unwrap(), "test");
+    })
+}
+
+#[test]
+fn builder_with_unknown_destination() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = OpenVPN3::builder()
+            .connection(server.connection().await.unwrap())
+            .destination(Service::Configuration, "org.example.missing")
+            .build()
+            .await
+            .unwrap();
+
+        assert!(openvpn3.import("test", CONFIG, true, false).await.is_err());
+    })
+}
+
+#[test]
+fn wait_for_services() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        openvpn3.wait_for_services(TIMEOUT).await.unwrap();
+
+        server.stop_service(Service::Log).await.unwrap();
+        assert_eq!(
+            openvpn3.wait_for_services(Duration::from_millis(500)).await,
+            Err(Error::ServiceUnavailable {
+                service: Service::Log
+            })
+        );
+    })
+}
+
+#[test]
+fn service_capabilities() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let versions = openvpn3.service_versions().await.unwrap();
+        assert_eq!(versions.get(Service::NetCfg), &ServiceVersion::parse("v21"));
+        assert!(openvpn3.supports_dco().await.unwrap());
+        assert!(openvpn3.supports_log_proxy().await.unwrap());
+        assert_eq!(openvpn3.dco_available().await, Ok(false));
+
+        // support for Data Channel Offload is cached per client
+        server.set_version("v16_beta");
+        assert!(openvpn3.supports_dco().await.unwrap());
+        let openvpn3 = server.client().await.unwrap();
+        assert!(!openvpn3.supports_dco().await.unwrap());
+        assert_eq!(
+            openvpn3.dco_available().await,
+            Err(Error::Unsupported(String::from("Data Channel Offload")))
+        );
+
+        let session = "/net/openvpn/v3/sessions/mock1".try_into().unwrap();
+        assert!(matches!(
+            openvpn3
+                .log_service()
+                .await
+                .unwrap()
+                .proxy_log_events(":1.1", &session)
+                .await,
+            Err(Error::Unsupported(_))
+        ));
+    })
+}
+
+#[test]
+fn dco_settings() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        assert_eq!(config.dco().await, Ok(false));
+        config.set_dco(true).await.unwrap();
+        assert_eq!(config.dco().await, Ok(true));
+
+        assert_eq!(session.dco().await, Ok(false));
+        session.set_dco(true).await.unwrap();
+        assert_eq!(session.dco().await, Ok(true));
+
+        // only the versions of the session manager and the network configuration service are needed
+        server.stop_service(Service::Log).await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        let session = openvpn3.sessions().await.unwrap().remove(0);
+        assert_eq!(session.dco().await, Ok(true));
+
+        // and they are fetched once per client
+        server.set_version("v16");
+        assert_eq!(session.dco().await, Ok(true));
+        assert_eq!(openvpn3.supports_dco().await, Ok(true));
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, false, false).await.unwrap();
+        let session = openvpn3.sessions().await.unwrap().remove(0);
+        let unsupported = || Error::Unsupported(String::from("Data Channel Offload"));
+        assert_eq!(openvpn3.supports_dco().await, Ok(false));
+        assert_eq!(config.dco().await, Err(unsupported()));
+        assert_eq!(config.set_dco(false).await, Err(unsupported()));
+        assert_eq!(session.dco().await, Err(unsupported()));
+        assert_eq!(session.set_dco(false).await, Err(unsupported()));
+    })
+}
+
+#[test]
+fn log_service_settings() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        let log_service = openvpn3.log_service().await.unwrap();
+
+        log_service.set_log_level(LogLevel::DEBUG).await.unwrap();
+        assert_eq!(log_service.log_level().await.unwrap(), LogLevel::DEBUG);
+
+        for value in [false, true] {
+            log_service.set_timestamp(value).await.unwrap();
+            assert_eq!(log_service.timestamp().await.unwrap(), value);
+            log_service.set_log_dbus_details(value).await.unwrap();
+            assert_eq!(log_service.log_dbus_details().await.unwrap(), value);
+            log_service.set_log_prefix_logtag(value).await.unwrap();
+            assert_eq!(log_service.log_prefix_logtag().await.unwrap(), value);
+        }
+
+        let session = "/net/openvpn/v3/sessions/mock1".try_into().unwrap();
+        let node = log_service
+            .proxy_log_events(":1.1", &session)
+            .await
+            .unwrap();
+        node.set_log_level(LogLevel::WARNING).await.unwrap();
+        assert_eq!(node.log_level().await.unwrap(), LogLevel::WARNING);
+        assert_eq!(node.session_path().await.unwrap().as_ref(), session);
+    })
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn verify_interfaces() {
+    use openvpn3_rs::diagnostics::{self, Difference, MemberKind, ObjectKind};
+
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let report = diagnostics::verify_interfaces(&openvpn3).await.unwrap();
+        let log = report.service(Service::Log).unwrap();
+        assert!(log.available);
+        assert!(log.differences.contains(&Difference::Missing {
+            kind: MemberKind::Method,
+            name: String::from("AssignSession"),
+        }));
+        assert!(!log
+            .differences
+            .iter()
+            .any(|difference| difference.to_string().contains("Attach")));
+        assert!(!report.is_compatible());
+
+        // nothing is created to check the objects managed by the services
+        assert!(report.node(Service::Configuration).is_none());
+        assert!(report.node(Service::Sessions).is_none());
+        assert!(report.node(Service::NetCfg).is_none());
+        assert_eq!(
+            report.unchecked,
+            vec![Service::Sessions, Service::Configuration, Service::NetCfg]
+        );
+        assert!(report
+            .to_string()
+            .contains("net.openvpn.v3.configuration node: not checked (no object available)"));
+        assert!(openvpn3.configurations().await.unwrap().is_empty());
+
+        let profile = openvpn3.import("test", CONFIG, false, false).await.unwrap();
+        let session = profile.new_tunnel().await.unwrap();
+        let report = diagnostics::verify_interfaces(&openvpn3).await.unwrap();
+        let config = report.node(Service::Configuration).unwrap();
+        assert!(config.available);
+        assert_eq!(config.object, ObjectKind::Node);
+        assert_eq!(report.unchecked, vec![Service::NetCfg]);
+        let node = report.node(Service::Sessions).unwrap();
+        assert!(node.available);
+        assert_eq!(node.path, session.path().as_str());
+
+        server.stop_service(Service::Log).await.unwrap();
+        let report = diagnostics::verify_interfaces(&openvpn3).await.unwrap();
+        assert!(!report.service(Service::Log).unwrap().available);
+        assert!(report.service(Service::Sessions).unwrap().available);
+    })
+}
+
+#[test]
+fn connect_with_credentials() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script("test", SessionScript::new().credentials().not_ready(2));
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        session
+            .connect_with(&CredentialSet::new("smith", "hunter2"), TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(session.state().await.unwrap(), SessionState::Connected);
+
+        let mock = server.session(session.path()).unwrap();
+        assert_eq!(
+            mock.provided_input(),
+            vec![
+                (
+                    ClientAttentionGroup::UserPassword,
+                    String::from("username"),
+                    String::from("smith")
+                ),
+                (
+                    ClientAttentionGroup::UserPassword,
+                    String::from("password"),
+                    String::from("hunter2")
+                ),
+            ]
+        );
+
+        session.disconnect().await.unwrap();
+        assert!(server.sessions().is_empty());
+    })
+}
+
+#[test]
+fn session_state_stream() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+        assert_eq!(session.state().await.unwrap(), SessionState::Created);
+
+        let mut states = Box::pin(session.state_stream().await.unwrap());
+        session.connect().await.unwrap();
+        assert_eq!(states.next().await, Some(SessionState::Connecting));
+        assert_eq!(states.next().await, Some(SessionState::Connected));
+
+        session.pause("test").await.unwrap();
+        assert_eq!(states.next().await, Some(SessionState::Paused));
+
+        session.resume().await.unwrap();
+        assert_eq!(states.next().await, Some(SessionState::Connecting));
+        assert_eq!(states.next().await, Some(SessionState::Connected));
+
+        session.restart().await.unwrap();
+        assert_eq!(states.next().await, Some(SessionState::Reconnecting));
+        assert_eq!(states.next().await, Some(SessionState::Connected));
+    })
+}
+
+#[test]
+fn wait_until_connected_backend_exits() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script(
+            "completed",
+            SessionScript::new().on_connect(vec![
+                MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnecting, ""),
+                MockEvent::status(StatusMajor::SESSION, StatusMinor::SessBackendCompleted, ""),
+            ]),
+        );
+        server.script(
+            "stopped",
+            SessionScript::new().on_connect(vec![MockEvent::status(
+                StatusMajor::PROCESS,
+                StatusMinor::ProcStopped,
+                "Backend process stopped",
+            )]),
+        );
+
+        let openvpn3 = server.client().await.unwrap();
+        for (name, expected) in [
+            (
+                "completed",
+                Error::ConnectionFailed(StatusMinor::SessBackendCompleted.to_string()),
+            ),
+            (
+                "stopped",
+                Error::BackendKilled(String::from("Backend process stopped")),
+            ),
+        ] {
+            let config = openvpn3.import(name, CONFIG, true, false).await.unwrap();
+            let session = config.new_tunnel().await.unwrap();
+            let started = std::time::Instant::now();
+
+            session.connect().await.unwrap();
+            assert_eq!(session.wait_until_connected(TIMEOUT).await, Err(expected));
+            assert!(started.elapsed() < TIMEOUT);
+        }
+    })
+}
+
+#[test]
+fn blocking_connect_with_credentials() {
+    let server = block_on(MockServer::start()).unwrap();
+    server.script("test", SessionScript::new().credentials());
+
+    let openvpn3 = blocking::OpenVPN3::new(block_on(server.connection()).unwrap().into()).unwrap();
+    let config = openvpn3.import("test", CONFIG, true, false).unwrap();
+    let session = config.new_tunnel().unwrap();
+    let mut states = session.state_stream().unwrap();
+
+    session
+        .connect_with(&CredentialSet::new("smith", "hunter2"), TIMEOUT)
+        .unwrap();
+    assert_eq!(session.state().unwrap(), SessionState::Connected);
+    assert!(states.any(|state| state == SessionState::Connected));
+
+    session.disconnect().unwrap();
+    assert!(openvpn3.sessions().unwrap().is_empty());
+}
+
+#[test]
+fn blocking_drop_within_runtime() {
+    let server = block_on(MockServer::start()).unwrap();
+
+    let openvpn3 = blocking::OpenVPN3::new(block_on(server.connection()).unwrap().into()).unwrap();
+    let config = openvpn3.import("test", CONFIG, true, false).unwrap();
+    let session = config.new_tunnel().unwrap();
+
+    // dropping must neither block nor start a nested runtime
+    block_on(async move {
+        drop(session);
+        drop(config);
+        drop(openvpn3);
+    });
+}
+
+#[test]
+fn connect_with_dynamic_challenge() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script(
+            "test",
+            SessionScript::new()
+                .credentials()
+                .on_connect(vec![
+                    MockEvent::RequireInput(MockUserInput::dynamic_challenge(
+                        "CRV1:R,E:state:c21pdGg=:Enter your OTP code",
+                    )),
+                    MockEvent::status(StatusMajor::SESSION, StatusMinor::SessAuthChallenge, ""),
+                ])
+                .on_connect(vec![MockEvent::status(
+                    StatusMajor::CONNECTION,
+                    StatusMinor::ConnConnected,
+                    "",
+                )]),
+        );
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        session
+            .connect_with(
+                &CredentialSet::new("smith", "hunter2").otp("123456"),
+                TIMEOUT,
+            )
+            .await
+            .unwrap();
+
+        let mock = server.session(session.path()).unwrap();
+        assert_eq!(mock.connect_count(), 2);
+        assert_eq!(
+            mock.provided_input().last().unwrap().2,
+            "CRV1::state::123456"
+        );
+    })
+}
+
+#[test]
+fn connect_with_auth_failure() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.default_script(SessionScript::new().credentials().on_connect(vec![
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnecting, ""),
+            MockEvent::status(
+                StatusMajor::CONNECTION,
+                StatusMinor::ConnAuthFailed,
+                "Invalid credentials",
+            ),
+        ]));
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        assert_eq!(
+            session
+                .connect_with(&CredentialSet::new("smith", "wrong"), TIMEOUT)
+                .await,
+            Err(Error::AuthFailed(String::from("Invalid credentials")))
+        );
+    })
+}
+
+#[test]
+fn authenticate_web() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.default_script(SessionScript::new().on_connect(vec![
+            MockEvent::attention(
+                ClientAttentionType::Credentials,
+                ClientAttentionGroup::OpenUrl,
+                "OPEN_URL:https://sso.example.com/login",
+            ),
+            MockEvent::Delay(Duration::from_millis(100)),
+            MockEvent::status(StatusMajor::CONNECTION, StatusMinor::ConnConnected, ""),
+        ]));
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        let opened = Mutex::new(Vec::new());
+        let opener = |request: &WebAuthRequest| {
+            opened.lock().unwrap().push(request.url().to_string());
+            Ok(())
+        };
+
+        session.ready().await.unwrap();
+        let authenticate = session.authenticate_web(&opener, TIMEOUT);
+        let connect = async {
+            sleep(Duration::from_millis(100)).await;
+            session.connect().await
+        };
+
+        let (authenticated, connected) = futures_util::join!(authenticate, connect);
+        connected.unwrap();
+        authenticated.unwrap();
+        assert_eq!(
+            opened.into_inner().unwrap(),
+            vec![String::from("https://sso.example.com/login")]
+        );
+    })
+}
+
+#[test]
+fn typed_errors() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script("test", SessionScript::new().credentials().not_ready(1));
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        assert_eq!(session.ready().await, Err(Error::BackendNotReady));
+        assert_eq!(session.ready().await, Err(Error::MissingUserCredentials));
+        assert_eq!(
+            session
+                .user_input_queue_fetch(
+                    ClientAttentionType::Credentials,
+                    ClientAttentionGroup::UserPassword,
+                    99
+                )
+                .await
+                .err(),
+            Some(Error::InvalidInput(String::from(
+                "No such user input request"
+            )))
+        );
+
+        let missing = Session::new(
+            openvpn3.connection().clone(),
+            "/net/openvpn/v3/sessions/missing".try_into().unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            missing.config_name().await,
+            Err(Error::NotFound(_))
+        ));
+    })
+}
+
+#[test]
+fn session_log_events() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+
+        let mut events = session.log_events().await.unwrap();
+        let mock = server.session(session.path()).unwrap();
+        assert!(mock.log_forwarding());
+
+        mock.emit_log(
+            LogGroup::CLIENT,
+            LogCategory::WARN,
+            "Certificate expires soon",
+        )
+        .await
+        .unwrap();
+
+        let event = events.next().await.unwrap();
+        assert_eq!(
+            event.source,
+            LogSource::Session(session.path().to_owned().into())
+        );
+        assert_eq!(event.severity, LogCategory::WARN);
+        assert_eq!(event.message, "Certificate expires soon");
+    })
+}
+
+#[test]
+fn record_session_until_removed() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script("test", SessionScript::new());
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+        let mock = server.session(session.path()).unwrap();
+
+        let directory =
+            std::env::temp_dir().join(format!("openvpn3-rs-record-session-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        let recorder = LogRecorder::new(&directory).unwrap();
+
+        let (recorded, ()) = futures_util::join!(recorder.record_session(&session), async {
+            // let the recorder subscribe first
+            sleep(Duration::from_millis(100)).await;
+            mock.emit_log(LogGroup::CLIENT, LogCategory::INFO, "Before removal")
+                .await
+                .unwrap();
+            session.disconnect().await.unwrap();
+        });
+        recorded.unwrap();
+
+        let records = LogReader::open(&directory)
+            .unwrap()
+            .read(&LogFilter::new())
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event.message, "Before removal");
+        assert_eq!(records[0].config_name.as_deref(), Some("test"));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    })
+}
+
+#[test]
+fn manager_log_events() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let mut events = openvpn3.all_logs().await.unwrap();
+        server
+            .emit_configuration_log(LogGroup::CONFIGMGR, LogCategory::CRIT, "Profile imported")
+            .await
+            .unwrap();
+
+        let event = events.next().await.unwrap();
+        assert_eq!(event.group, LogGroup::CONFIGMGR);
+        assert_eq!(event.severity, LogCategory::CRIT);
+        assert_eq!(event.message, "Profile imported");
+
+        // Log signals of session objects are included, even for sessions created after the stream
+        server.script("test", SessionScript::new());
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+        server
+            .session(session.path())
+            .unwrap()
+            .emit_log(LogGroup::CLIENT, LogCategory::WARN, "Session log")
+            .await
+            .unwrap();
+
+        let event = events.next().await.unwrap();
+        assert_eq!(
+            event.source,
+            LogSource::Session(session.path().to_owned().into())
+        );
+        assert_eq!(event.group, LogGroup::CLIENT);
+        assert_eq!(event.message, "Session log");
+    })
+}
+
+#[test]
+fn sample_statistics() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server.script("test", SessionScript::new());
+
+        let openvpn3 = server.client().await.unwrap();
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        let session = config.new_tunnel().await.unwrap();
+        let mock = server.session(session.path()).unwrap();
+
+        mock.set_statistics(HashMap::from([
+            (String::from("BYTES_IN"), 1000),
+            (String::from("PACKETS_IN"), 10),
+            (String::from("KEEPALIVE_TIMEOUT"), 0),
+        ]));
+
+        let mut samples =
+            Box::pin(StatisticsSampler::new(&session, Duration::from_millis(50)).into_stream());
+        let first = samples.next().await.unwrap().unwrap();
+        assert_eq!(first.statistics.bytes_in, 1000);
+        assert_eq!(first.statistics.extra.get("KEEPALIVE_TIMEOUT"), Some(&0));
+        assert_eq!(first.rates, None);
+
+        mock.set_statistics(HashMap::from([
+            (String::from("BYTES_IN"), 5000),
+            (String::from("PACKETS_IN"), 20),
+        ]));
+
+        let second = samples.next().await.unwrap().unwrap();
+        assert!(second.taken_at - first.taken_at >= Duration::from_millis(50));
+
+        let rates = second.rates.unwrap();
+        assert!(rates.bytes_in > 0.0);
+        assert!(rates.packets_in > 0.0);
+        assert_eq!(rates.bytes_out, 0.0);
+    })
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn export_metrics() {
+    use openvpn3_rs::metrics;
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+    };
+
+    let server = block_on(MockServer::start()).unwrap();
+    server.script("My \"VPN\"", SessionScript::new());
+
+    let openvpn3 = block_on(server.client()).unwrap();
+    let config = block_on(openvpn3.import("My \"VPN\"", CONFIG, true, false)).unwrap();
+    let session = block_on(config.new_tunnel()).unwrap();
+    let mock = server.session(session.path()).unwrap();
+    mock.set_device_name("tun0");
+    mock.set_statistics(HashMap::from([
+        (String::from("BYTES_OUT"), 4096),
+        (String::from("KEEPALIVE_TIMEOUT"), 2),
+    ]));
+
+    let labels = format!(
+        "config=\"My \\\"VPN\\\"\",device=\"tun0\",path=\"{}\"",
+        session.path()
+    );
+    let text = block_on(metrics::render(&openvpn3)).unwrap();
+    assert!(text.contains(&format!(
+        "openvpn3_session_status{{{},status=\"CfgOk\"}} 2\n",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "openvpn3_session_bytes_out_total{{{}}} 4096\n",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "openvpn3_session_statistic{{{},name=\"KEEPALIVE_TIMEOUT\"}} 2\n",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "openvpn3_session_backend_pid{{{}}} {}\n",
+        labels,
+        std::process::id()
+    )));
+
+    assert!(text.contains(&format!("openvpn3_session_dco{{{}}} 0\n", labels)));
+    assert!(text.contains("openvpn3_scrape_failed_sessions 0\n"));
+
+    // the dco property is not read from services without Data Channel Offload support
+    server.set_version("v16");
+    let text = block_on(async { metrics::render(&server.client().await?).await }).unwrap();
+    assert!(!text.contains("openvpn3_session_dco{"));
+    assert!(text.contains("openvpn3_session_bytes_out_total{"));
+    server.set_version("v21");
+
+    let path = std::env::temp_dir().join(format!("openvpn3-rs-{}.prom", std::process::id()));
+    block_on(metrics::write_textfile(&openvpn3, &path)).unwrap();
+    assert!(std::fs::read_to_string(&path)
+        .unwrap()
+        .contains("openvpn3_session_bytes_out_total"));
+    std::fs::remove_file(&path).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let blocking = blocking::OpenVPN3::from(openvpn3);
+    std::thread::spawn(move || metrics::serve(&blocking, listener));
+
+    let get = |target: &str| {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let response = get("/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains(metrics::CONTENT_TYPE));
+    assert!(response.ends_with("# EOF\n"));
+    assert!(get("/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+    // a dco property which cannot be read does not drop the session
+    block_on(server.stop_service(Service::NetCfg)).unwrap();
+    let text = block_on(async { metrics::render(&server.client().await?).await }).unwrap();
+    assert!(!text.contains("openvpn3_session_dco{"));
+    assert!(text.contains(&format!(
+        "openvpn3_session_bytes_out_total{{{}}} 4096\n",
+        labels
+    )));
+    assert!(text.contains("openvpn3_scrape_failed_sessions 0\n"));
+}
+
+#[test]
+fn import_profile() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let mut profile = Profile::parse(
+            "client\n\
+             dev tun\n\
+             remote vpn.example.com 1194 udp\n\
+             route 10.0.0.0 255.0.0.0\n\
+             static-challenge \"Enter PIN\" 1\n\
+             tls-auth ta.key 1\n\
+             <ca>\n\
+             -----BEGIN CERTIFICATE-----\n\
+             </ca>\n",
+        )
+        .unwrap();
+
+        profile
+            .routes
+            .push(Route::new("192.168.0.0", "255.255.0.0"));
+        let config = openvpn3
+            .import_profile("test", &profile, true, false)
+            .await
+            .unwrap();
+        assert_eq!(config.profile().await.unwrap(), profile);
+        assert_eq!(config.fetch().await.unwrap(), profile.to_string());
+    })
+}
+
+#[test]
+fn import_file() {
+    let directory = std::env::temp_dir().join(format!("openvpn3-rs-import-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(directory.join("keys")).unwrap();
+    std::fs::write(directory.join("ca.crt"), "CA\n").unwrap();
+    std::fs::write(directory.join("keys/ta.key"), "TA\n").unwrap();
+    std::fs::write(directory.join("creds.txt"), "smith\nhunter2\n").unwrap();
+    std::fs::write(
+        directory.join("office.ovpn"),
+        "client\n\
+         remote vpn.example.com\n\
+         ca ca.crt\n\
+         key client.key\n\
+         tls-crypt keys/ta.key\n\
+         auth-user-pass creds.txt\n",
+    )
+    .unwrap();
+
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        match openvpn3
+            .import_file(directory.join("office.ovpn"), true, false)
+            .await
+        {
+            Err(Error::UnreadableFiles(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].directive, "key");
+                assert_eq!(errors[0].path, directory.join("client.key"));
+                assert_eq!(errors[0].kind, std::io::ErrorKind::NotFound);
+            }
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+
+        std::fs::write(directory.join("client.key"), "KEY\n").unwrap();
+        let config = openvpn3
+            .import_file(directory.join("office.ovpn"), true, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            config.get_property::<String>("name").await.unwrap(),
+            "office"
+        );
+
+        let profile = config.profile().await.unwrap();
+        assert_eq!(profile.ca, Some(Material::Inline(String::from("CA\n"))));
+        assert_eq!(
+            profile.tls_crypt,
+            Some(Material::Inline(String::from("TA\n")))
+        );
+        assert_eq!(
+            profile.auth_user_pass,
+            Some(AuthUserPass::Inline(String::from("smith\nhunter2\n")))
+        );
+    });
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn validate_profile() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let config = openvpn3
+            .import("test", "remote vpn.example.com\nca ca.crt\n", true, false)
+            .await
+            .unwrap();
+        let report = config.validate().await.unwrap();
+        assert_eq!(
+            report.errors().next().unwrap().to_string(),
+            "line 2: error: ca ca.crt must be embedded inline, OpenVPN 3 cannot read files"
+        );
+
+        let config = openvpn3
+            .import(
+                "test",
+                "remote vpn.example.com\n<ca>\nCA\n</ca>\n",
+                true,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(config.validate().await.unwrap().is_valid());
+    })
+}
+
+#[test]
+fn configuration_overrides() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        assert!(config.overrides().await.unwrap().is_empty());
+
+        for setting in [
+            ConfigOverride::PortOverride(443),
+            ConfigOverride::ProtoOverride(Proto::Tcp),
+            ConfigOverride::Ipv6(Ipv6Mode::No),
+            ConfigOverride::DnsSyncLookup(true),
+            ConfigOverride::AllowCompression(Compression::Asym),
+        ] {
+            config.set_override(&setting).await.unwrap();
+        }
+        config
+            .set_override(&ConfigOverride::PortOverride(1194))
+            .await
+            .unwrap();
+        assert_eq!(
+            config.overrides().await.unwrap(),
+            vec![
+                ConfigOverride::AllowCompression(Compression::Asym),
+                ConfigOverride::DnsSyncLookup(true),
+                ConfigOverride::Ipv6(Ipv6Mode::No),
+                ConfigOverride::PortOverride(1194),
+                ConfigOverride::ProtoOverride(Proto::Tcp),
+            ]
+        );
+
+        config.unset_override(OverrideKind::Ipv6).await.unwrap();
+        assert!(!config
+            .overrides()
+            .await
+            .unwrap()
+            .contains(&ConfigOverride::Ipv6(Ipv6Mode::No)));
+        assert!(matches!(
+            config.unset_override(OverrideKind::Ipv6).await,
+            Err(Error::ConfigurationManager(_))
+        ));
+
+        // a value which cannot be decoded is skipped instead of failing the listing
+        let path = server.add_configuration("invalid", CONFIG).await.unwrap();
+        for (name, value) in [
+            ("port-override", OwnedValue::from(Str::from("https"))),
+            ("dns-sync-lookup", OwnedValue::from(true)),
+        ] {
+            openvpn3
+                .connection()
+                .call_method(
+                    Some("net.openvpn.v3.configuration"),
+                    &path,
+                    Some("net.openvpn.v3.configuration"),
+                    "SetOverride",
+                    &(name, &*value),
+                )
+                .await
+                .unwrap();
+        }
+        let configs = openvpn3.configurations().await.unwrap();
+        let mut listed = Vec::new();
+        for config in &configs {
+            listed.push(config.overrides().await.unwrap());
+        }
+        assert!(listed.contains(&vec![ConfigOverride::DnsSyncLookup(true)]));
+    });
+
+    assert!(matches!(
+        ConfigOverride::from_value(
+            OverrideKind::PortOverride,
+            &OwnedValue::from(Str::from("https"))
+        ),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(
+        ConfigOverride::from_value(OverrideKind::DnsSyncLookup, &OwnedValue::from(1u32)).is_err()
+    );
+}
+
+#[cfg(feature = "users")]
+#[test]
+fn configuration_access() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        config.transfer_to(4242).await.unwrap();
+        config.grant("root").await.unwrap();
+        config.grant(User::Uid(4343)).await.unwrap();
+        config.revoke(4343).await.unwrap();
+        assert_eq!(
+            config.grant("no-such-user").await,
+            Err(Error::UnknownUser(String::from("no-such-user")))
+        );
+
+        let access = config.access().await.unwrap();
+        assert_eq!(
+            access.owner,
+            AccessEntry {
+                uid: 4242,
+                name: None
+            }
+        );
+        assert_eq!(
+            access.acl,
+            vec![AccessEntry {
+                uid: 0,
+                name: Some(String::from("root"))
+            }]
+        );
+        assert!(access.has_access(0));
+        assert!(!access.has_access(4343));
+        assert!(!access.public_access && !access.locked_down && !access.sealed);
+
+        config.set_public(true).await.unwrap();
+        config.lock_down(true).await.unwrap();
+        config.seal().await.unwrap();
+        assert_eq!(
+            config.access().await.unwrap().to_string(),
+            "owner: 4242\n\
+             granted: root (0)\n\
+             public access: yes\n\
+             locked down: yes\n\
+             sealed: yes\n"
+        );
+    })
+}
+
+#[cfg(not(feature = "users"))]
+#[test]
+fn configuration_access_by_uid() {
+    block_on(async {
+        let server = MockServer::start().await.unwrap();
+        let openvpn3 = server.client().await.unwrap();
+
+        let config = openvpn3.import("test", CONFIG, true, false).await.unwrap();
+        assert!(matches!(
+            config.grant("root").await,
+            Err(Error::InvalidInput(_))
+        ));
+        config.grant(User::Uid(0)).await.unwrap();
+
+        let access = config.access().await.unwrap();
+        assert_eq!(access.acl, vec![AccessEntry { uid: 0, name: None }]);
+    })
+}